@@ -0,0 +1,138 @@
+use std::fmt::Display;
+use std::io::Read;
+use std::process::ExitCode;
+use std::{error, fmt, fs, io};
+
+/// Path that stands for standard input.
+pub const STDIN: &str = "-";
+
+#[derive(Debug)]
+pub struct InputError {
+    pub path: String,
+    pub source: io::Error,
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path == STDIN {
+            write!(f, "cannot read input from stdin: {}", self.source)
+        } else {
+            write!(f, "cannot read input {}: {}", self.path, self.source)
+        }
+    }
+}
+
+impl error::Error for InputError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Reads a puzzle input from `path`, or from stdin if `path` is [`STDIN`].
+pub fn read(path: &str) -> Result<String, InputError> {
+    let result = if path == STDIN {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input).map(|_| input)
+    } else {
+        fs::read_to_string(path)
+    };
+
+    result.map_err(|source| InputError {
+        path: path.to_string(),
+        source,
+    })
+}
+
+/// Input paths from command line arguments, or `default` if there are none.
+pub fn paths(args: impl IntoIterator<Item = String>, default: &str) -> Vec<String> {
+    let paths = args.into_iter().collect::<Vec<_>>();
+
+    if paths.is_empty() {
+        vec![default.to_string()]
+    } else {
+        paths
+    }
+}
+
+/// Removes every occurrence of the switch `flag` from `args`, returning whether it was there.
+pub fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    let len = args.len();
+    args.retain(|arg| arg != flag);
+    args.len() != len
+}
+
+/// Solves every input in turn and prints the answers.
+///
+/// A single answer is printed bare; with several inputs each answer is prefixed with its path.
+/// Unreadable inputs and solver errors are reported on stderr without stopping the others.
+pub fn solve_all<E: Display>(
+    paths: &[String],
+    solver: impl Fn(&str) -> Result<String, E>,
+) -> ExitCode {
+    solve_all_with_warnings(paths, |input| {
+        solver(input).map(|answer| (answer, Vec::<String>::new()))
+    })
+}
+
+/// Like [`solve_all`], for solvers that also return warnings about an input.
+///
+/// Warnings are reported on stderr, prefixed with the input's path, and do not fail the run.
+pub fn solve_all_with_warnings<E: Display, W: Display>(
+    paths: &[String],
+    solver: impl Fn(&str) -> Result<(String, Vec<W>), E>,
+) -> ExitCode {
+    let mut status = ExitCode::SUCCESS;
+
+    for path in paths {
+        let name = if path == STDIN { "stdin" } else { path };
+        let result = read(path)
+            .map_err(|e| e.to_string())
+            .and_then(|input| solver(&input).map_err(|e| format!("{name}: {e}")));
+
+        match result {
+            Ok((answer, warnings)) => {
+                for warning in warnings {
+                    eprintln!("{name}: {warning}");
+                }
+                if paths.len() == 1 {
+                    println!("{answer}");
+                } else {
+                    println!("{name}: {answer}");
+                }
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                status = ExitCode::FAILURE;
+            }
+        }
+    }
+
+    status
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths_fall_back_to_default() {
+        assert_eq!(paths(vec![], "./input.txt"), vec!["./input.txt"]);
+        assert_eq!(paths(vec!["-".to_string()], "./input.txt"), vec!["-"]);
+    }
+
+    #[test]
+    fn flags_are_taken_out() {
+        let mut args = vec!["--lenient".to_string(), "input.txt".to_string()];
+        assert!(take_flag(&mut args, "--lenient"));
+        assert!(!take_flag(&mut args, "--lenient"));
+        assert_eq!(args, ["input.txt"]);
+    }
+
+    #[test]
+    fn missing_file_names_path() {
+        let error = read("does/not/exist.txt").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("cannot read input does/not/exist.txt: "));
+    }
+}
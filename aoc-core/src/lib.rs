@@ -0,0 +1,217 @@
+pub mod input;
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+
+use nom::IResult;
+use tracing::info_span;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Part {
+    One = 1,
+    Two = 2,
+}
+
+impl TryFrom<u8> for Part {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            _ => Err(format!("part must be 1 or 2, got {value}")),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&(*self as u8), f)
+    }
+}
+
+/// 1-based line and column of a token in the puzzle input, with columns counted in
+/// characters.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    /// Locates `token`, which must be a subslice of `input`.
+    pub fn of(input: &str, token: &str) -> Self {
+        let offset = token.as_ptr() as usize - input.as_ptr() as usize;
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+
+        Position {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// Text a parser could not make sense of, with the line it starts as the token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxError {
+    pub position: Position,
+    pub token: String,
+}
+
+impl SyntaxError {
+    /// Locates `rest`, a subslice of `input`, skipping leading whitespace.
+    pub fn at(input: &str, rest: &str) -> Self {
+        let rest = rest.trim_start();
+
+        SyntaxError {
+            position: Position::of(input, rest),
+            token: rest.lines().next().unwrap_or_default().to_string(),
+        }
+    }
+}
+
+impl Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unexpected {:?} at {}", self.token, self.position)
+    }
+}
+
+impl Error for SyntaxError {}
+
+/// Output of a nom parser run over `input`, which must consume all of it but trailing
+/// whitespace.
+pub fn parse_all<'a, O>(input: &'a str, result: IResult<&'a str, O>) -> Result<O, SyntaxError> {
+    let (rest, output) = result.map_err(|e| match e {
+        nom::Err::Error(e) | nom::Err::Failure(e) => SyntaxError::at(input, e.input),
+        nom::Err::Incomplete(_) => SyntaxError::at(input, &input[input.len()..]),
+    })?;
+
+    if !rest.trim().is_empty() {
+        return Err(SyntaxError::at(input, rest));
+    }
+
+    Ok(output)
+}
+
+/// A single day's puzzle.
+///
+/// The input is parsed once into [`Solution::Input`], which both parts then work from.
+/// Implementors are usually unit structs, e.g. `pub struct Day01;`.
+pub trait Solution {
+    /// Day of the advent calendar, starting at 1.
+    const DAY: u8;
+    /// Puzzle title as shown on the calendar.
+    const TITLE: &'static str;
+
+    /// Parsed puzzle input, which may borrow from the raw text.
+    type Input<'a>;
+    type Output1: Display;
+    type Output2: Display;
+    /// Malformed input, or input the puzzle has no answer for.
+    type Error: Error + Send + Sync + 'static;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error>;
+    fn part1(input: &Self::Input<'_>) -> Result<Self::Output1, Self::Error>;
+    fn part2(input: &Self::Input<'_>) -> Result<Self::Output2, Self::Error>;
+
+    /// Parses `input` and solves `part`, formatting the answer.
+    ///
+    /// Runs inside a `solve` span, with child spans for the `parse` and `part` phases.
+    fn solve(part: Part, input: &str) -> Result<String, Self::Error> {
+        let _solve = info_span!("solve", day = Self::DAY, %part).entered();
+
+        let input = info_span!("parse", bytes = input.len()).in_scope(|| Self::parse(input))?;
+
+        let result = info_span!("part", %part).in_scope(|| match part {
+            Part::One => Self::part1(&input).map(|answer| answer.to_string()),
+            Part::Two => Self::part2(&input).map(|answer| answer.to_string()),
+        })?;
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::num::ParseIntError;
+
+    struct Lines;
+
+    impl Solution for Lines {
+        const DAY: u8 = 0;
+        const TITLE: &'static str = "Lines";
+
+        type Input<'a> = Vec<&'a str>;
+        type Output1 = usize;
+        type Output2 = String;
+        type Error = ParseIntError;
+
+        fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+            Ok(input.lines().collect())
+        }
+
+        fn part1(input: &Self::Input<'_>) -> Result<Self::Output1, Self::Error> {
+            Ok(input.len())
+        }
+
+        fn part2(input: &Self::Input<'_>) -> Result<Self::Output2, Self::Error> {
+            input
+                .iter()
+                .map(|line| line.parse::<u32>())
+                .sum::<Result<u32, _>>()?;
+            Ok(input.concat())
+        }
+    }
+
+    #[test]
+    fn solve_parses_once_per_part() {
+        assert_eq!(Lines::solve(Part::One, "1\n2\n3").unwrap(), "3");
+        assert_eq!(Lines::solve(Part::Two, "1\n2\n3").unwrap(), "123");
+        assert!(Lines::solve(Part::Two, "1\nb\n3").is_err());
+    }
+
+    #[test]
+    fn position_of_token() {
+        let input = "abc\ndef\nghi";
+        let position = Position::of(input, &input[9..]);
+        assert_eq!(position, Position { line: 3, column: 2 });
+
+        let input = "ab\néé x";
+        let position = Position::of(input, &input[input.len() - 1..]);
+        assert_eq!(position, Position { line: 2, column: 4 });
+    }
+
+    #[test]
+    fn parse_all_locates_leftovers() {
+        let input = "12\n 3x\n";
+        let digits = |input| nom::character::complete::digit1::<_, nom::error::Error<_>>(input);
+
+        assert_eq!(
+            parse_all(input, digits(input)),
+            Err(SyntaxError::at(input, &input[2..]))
+        );
+        assert_eq!(
+            parse_all(&input[3..], digits(&input[3..]))
+                .unwrap_err()
+                .to_string(),
+            "unexpected \"3x\" at line 1, column 2"
+        );
+        assert_eq!(parse_all("12\n", digits("12\n")), Ok("12"));
+    }
+
+    #[test]
+    fn part_from_number() {
+        assert_eq!(Part::try_from(2), Ok(Part::Two));
+        assert!(Part::try_from(3).is_err());
+    }
+}
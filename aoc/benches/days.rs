@@ -0,0 +1,57 @@
+use std::hint::black_box;
+
+use aoc_core::Solution;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use day_01::Day01;
+use day_02::Day02;
+use day_03::Day03;
+use day_04::Day04;
+use day_05::Day05;
+use day_06::Day06;
+use day_07::Day07;
+use day_08::Day08;
+use day_09::Day09;
+
+/// Benchmarks parsing and both parts of `S` separately on the given input.
+fn bench_solution<S: Solution>(c: &mut Criterion, name: &str, input: &str) {
+    let mut group = c.benchmark_group(format!("day-{:02}/{name}", S::DAY));
+
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(input)).unwrap()));
+
+    let parsed = S::parse(input).unwrap();
+    group.bench_function("part1", |b| {
+        b.iter(|| S::part1(black_box(&parsed)).unwrap())
+    });
+    group.bench_function("part2", |b| {
+        b.iter(|| S::part2(black_box(&parsed)).unwrap())
+    });
+
+    group.finish();
+}
+
+fn bench_day<S: Solution>(c: &mut Criterion, example: &str, large: &str) {
+    bench_solution::<S>(c, "example", example);
+    bench_solution::<S>(c, "large", large);
+}
+
+fn days(c: &mut Criterion) {
+    const SEED: u64 = 2022;
+
+    bench_day::<Day01>(c, day_01::EXAMPLE, &day_01::generate::input(SEED, 20_000));
+    bench_day::<Day02>(c, day_02::EXAMPLE, &day_02::generate::input(SEED, 50_000));
+    bench_day::<Day03>(c, day_03::EXAMPLE, &day_03::generate::input(SEED, 20_000));
+    bench_day::<Day04>(c, day_04::EXAMPLE, &day_04::generate::input(SEED, 50_000));
+    bench_day::<Day05>(c, day_05::EXAMPLE, &day_05::generate::input(SEED, 20_000));
+    bench_day::<Day06>(c, day_06::EXAMPLE, &day_06::generate::input(SEED, 300_000));
+    bench_day::<Day07>(c, day_07::EXAMPLE, &day_07::generate::input(SEED, 5_000));
+    bench_day::<Day08>(c, day_08::EXAMPLE, &day_08::generate::input(SEED, 300));
+    bench_day::<Day09>(
+        c,
+        day_09::LARGER_EXAMPLE,
+        &day_09::generate::input(SEED, 10_000),
+    );
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
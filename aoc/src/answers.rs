@@ -0,0 +1,113 @@
+use std::error::Error;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Part;
+
+/// Default location of the answer registry, relative to the workspace root.
+pub const DEFAULT_PATH: &str = "answers.toml";
+
+/// Known-good answer for one part of a day, for the input with the given hash.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Answer {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub answer: String,
+}
+
+/// Registry of known-good answers, stored as TOML.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Answers {
+    #[serde(default, rename = "answer")]
+    entries: Vec<Answer>,
+}
+
+impl Answers {
+    /// Reads the registry at `path`, treating a missing file as empty.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(toml::from_str(&content)?),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error + Send + Sync>> {
+        fs::write(path, toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, day: u8, part: Part, input: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|entry| entry.day == day && entry.part == part as u8 && entry.input == input)
+            .map(|entry| entry.answer.as_str())
+    }
+
+    /// Records `answer`, replacing any previous one for the same day, part and input.
+    pub fn insert(&mut self, day: u8, part: Part, input: &str, answer: &str) {
+        let entry = Answer {
+            day,
+            part: part as u8,
+            input: input.to_string(),
+            answer: answer.to_string(),
+        };
+
+        match self
+            .entries
+            .iter_mut()
+            .find(|e| e.day == day && e.part == part as u8 && e.input == input)
+        {
+            Some(existing) => *existing = entry,
+            None => {
+                self.entries.push(entry);
+                self.entries.sort_by_key(|e| (e.day, e.part));
+            }
+        }
+    }
+}
+
+/// Stable 64-bit FNV-1a hash of a puzzle input, as hex.
+pub fn input_hash(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_replaces_existing_answer() {
+        let mut answers = Answers::default();
+        answers.insert(1, Part::One, "abc", "1");
+        answers.insert(1, Part::One, "abc", "2");
+        answers.insert(1, Part::Two, "abc", "3");
+
+        assert_eq!(answers.get(1, Part::One, "abc"), Some("2"));
+        assert_eq!(answers.get(1, Part::Two, "abc"), Some("3"));
+        assert_eq!(answers.get(1, Part::One, "def"), None);
+    }
+
+    #[test]
+    fn round_trips_through_toml() {
+        let mut answers = Answers::default();
+        answers.insert(5, Part::Two, &input_hash("input"), "MCD");
+
+        let content = toml::to_string(&answers).unwrap();
+        assert_eq!(toml::from_str::<Answers>(&content).unwrap(), answers);
+    }
+
+    #[test]
+    fn input_hash_is_fnv1a() {
+        assert_eq!(input_hash(""), "cbf29ce484222325");
+        assert_eq!(input_hash("a"), "af63dc4c8601ec8c");
+    }
+}
@@ -0,0 +1,311 @@
+use std::fs;
+use std::io;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use thiserror::Error;
+use ureq::{Agent, Response};
+
+/// Puzzle year the runner talks to.
+pub const YEAR: u16 = 2022;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Minimum time between two requests to the server, shared by every `aoc` process.
+pub const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+const USER_AGENT: &str = "advent-of-code-2022 aoc runner";
+
+/// Name of the file in the cache directory holding the time of the last request.
+const LAST_REQUEST: &str = ".last-request";
+
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error("no session token, pass --session or set AOC_SESSION")]
+    MissingSession,
+    #[error("session token was rejected, copy a fresh `session` cookie from the browser")]
+    InvalidSession,
+    #[error("day {day} is not available yet")]
+    NotFound { day: u8 },
+    #[error("rate limited by the server, try again later")]
+    RateLimited,
+    #[error("unexpected answer from the server: {0}")]
+    UnexpectedResponse(String),
+    #[error("server answered {status}: {body}")]
+    Http { status: u16, body: String },
+    #[error("request failed: {0}")]
+    Transport(Box<ureq::Transport>),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// Per-user cache directory for puzzle inputs, if the platform has one.
+pub fn default_cache_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("aoc").join(YEAR.to_string()))
+}
+
+/// Blocking client for the puzzle server, caching inputs on disk.
+pub struct Client {
+    agent: Agent,
+    base_url: String,
+    session: Option<String>,
+    cache_dir: PathBuf,
+    min_interval: Duration,
+}
+
+impl Client {
+    pub fn new(session: Option<String>, cache_dir: impl Into<PathBuf>) -> Self {
+        Client {
+            agent: Agent::new(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            session,
+            cache_dir: cache_dir.into(),
+            min_interval: DEFAULT_MIN_INTERVAL,
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir
+    }
+
+    /// Location of the cached input for `day`.
+    pub fn input_path(&self, day: u8) -> PathBuf {
+        self.cache_dir.join(format!("day-{day:02}.txt"))
+    }
+
+    /// Returns the path of the input for `day`, downloading it unless already cached.
+    ///
+    /// The download is written next to the cache and moved into place once complete, so an
+    /// interrupted fetch never leaves a truncated input behind to be served later.
+    pub fn fetch_input(&self, day: u8) -> Result<PathBuf, ClientError> {
+        let path = self.input_path(day);
+        if path.exists() {
+            return Ok(path);
+        }
+
+        let input = self
+            .get(&format!("/{YEAR}/day/{day}/input"))
+            .map_err(|e| match e {
+                ClientError::Http { status: 404, .. } => ClientError::NotFound { day },
+                e => e,
+            })?;
+
+        if input.is_empty() {
+            return Err(ClientError::UnexpectedResponse("empty input".to_string()));
+        }
+
+        fs::create_dir_all(&self.cache_dir)?;
+        let partial = self
+            .cache_dir
+            .join(format!(".day-{day:02}.{}.part", process::id()));
+        fs::write(&partial, input)
+            .and_then(|()| fs::rename(&partial, &path))
+            .inspect_err(|_| {
+                let _ = fs::remove_file(&partial);
+            })?;
+        Ok(path)
+    }
+
+    pub(crate) fn get(&self, path: &str) -> Result<String, ClientError> {
+        let session = self.session()?;
+        self.throttle()?;
+
+        let request = self
+            .agent
+            .get(&format!("{}{path}", self.base_url))
+            .set("Cookie", &format!("session={session}"))
+            .set("User-Agent", USER_AGENT);
+
+        read_response(request.call())
+    }
+
+    pub(crate) fn post(&self, path: &str, form: &[(&str, &str)]) -> Result<String, ClientError> {
+        let session = self.session()?;
+        self.throttle()?;
+
+        let request = self
+            .agent
+            .post(&format!("{}{path}", self.base_url))
+            .set("Cookie", &format!("session={session}"))
+            .set("User-Agent", USER_AGENT);
+
+        read_response(request.send_form(form))
+    }
+
+    fn session(&self) -> Result<&str, ClientError> {
+        match self.session.as_deref().map(str::trim) {
+            Some(session) if !session.is_empty() => Ok(session),
+            _ => Err(ClientError::MissingSession),
+        }
+    }
+
+    /// Sleeps until `min_interval` has passed since the last request, then records this one.
+    fn throttle(&self) -> Result<(), ClientError> {
+        let stamp = self.cache_dir.join(LAST_REQUEST);
+        let last = match fs::read_to_string(&stamp) {
+            Ok(content) => content.trim().parse::<u64>().ok(),
+            Err(e) if e.kind() == ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+
+        if let Some(last) = last {
+            // The stamp is truncated to the millisecond, so count from the end of that one.
+            let elapsed = now().saturating_sub(Duration::from_millis(last + 1));
+            if let Some(wait) = self.min_interval.checked_sub(elapsed) {
+                thread::sleep(wait);
+            }
+        }
+
+        fs::create_dir_all(&self.cache_dir)?;
+        fs::write(&stamp, now().as_millis().to_string())?;
+        Ok(())
+    }
+}
+
+/// Time since the Unix epoch.
+pub(crate) fn now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+fn read_response(result: Result<Response, ureq::Error>) -> Result<String, ClientError> {
+    match result {
+        Ok(response) => Ok(response.into_string()?),
+        Err(ureq::Error::Status(status, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            Err(match status {
+                400 => ClientError::InvalidSession,
+                429 => ClientError::RateLimited,
+                _ => ClientError::Http {
+                    status,
+                    body: body.trim().to_string(),
+                },
+            })
+        }
+        Err(ureq::Error::Transport(transport)) => Err(ClientError::Transport(Box::new(transport))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+    use crate::mock::{temp_dir, MockServer};
+
+    fn client(server: &MockServer, cache: &Path) -> Client {
+        Client::new(Some("53cr3t".to_string()), cache)
+            .with_base_url(&server.url)
+            .with_min_interval(Duration::ZERO)
+    }
+
+    #[test]
+    fn fetch_downloads_and_caches_input() {
+        let cache = temp_dir("fetch-caches");
+        let server = MockServer::start(|_| (200, "1000\n2000\n".to_string()));
+        let client = client(&server, &cache);
+
+        let path = client.fetch_input(1).unwrap();
+        assert_eq!(path, cache.join("day-01.txt"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1000\n2000\n");
+
+        assert_eq!(client.fetch_input(1).unwrap(), path);
+        let cached: Vec<_> = fs::read_dir(&cache)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .filter(|name| name != LAST_REQUEST)
+            .collect();
+        assert_eq!(cached, ["day-01.txt"]);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/2022/day/1/input");
+        assert_eq!(requests[0].cookie.as_deref(), Some("session=53cr3t"));
+    }
+
+    #[test]
+    fn fetch_does_not_cache_empty_input() {
+        let cache = temp_dir("fetch-empty");
+        let server = MockServer::start(|_| (200, String::new()));
+        let client = client(&server, &cache);
+
+        assert!(matches!(
+            client.fetch_input(1),
+            Err(ClientError::UnexpectedResponse(_))
+        ));
+        assert!(!client.input_path(1).exists());
+    }
+
+    #[test]
+    fn fetch_requires_session() {
+        let cache = temp_dir("fetch-session");
+        let server = MockServer::start(|_| (200, String::new()));
+        let client = Client::new(None, &cache).with_base_url(&server.url);
+
+        assert!(matches!(
+            client.fetch_input(1),
+            Err(ClientError::MissingSession)
+        ));
+        assert!(server.requests().is_empty());
+    }
+
+    #[test]
+    fn fetch_maps_error_statuses() {
+        let cache = temp_dir("fetch-errors");
+        let server = MockServer::start(|request| match request.path.as_str() {
+            "/2022/day/1/input" => (400, "Please log in.".to_string()),
+            "/2022/day/2/input" => (404, "Not Found".to_string()),
+            "/2022/day/3/input" => (429, String::new()),
+            _ => (500, "Internal Server Error\n".to_string()),
+        });
+        let client = client(&server, &cache);
+
+        assert!(matches!(
+            client.fetch_input(1),
+            Err(ClientError::InvalidSession)
+        ));
+        assert!(matches!(
+            client.fetch_input(2),
+            Err(ClientError::NotFound { day: 2 })
+        ));
+        assert!(matches!(
+            client.fetch_input(3),
+            Err(ClientError::RateLimited)
+        ));
+        assert_eq!(
+            client.fetch_input(4).unwrap_err().to_string(),
+            "server answered 500: Internal Server Error"
+        );
+        assert!(!client.input_path(1).exists());
+    }
+
+    #[test]
+    fn requests_are_throttled() {
+        let cache = temp_dir("fetch-throttle");
+        let server = MockServer::start(|_| (200, "input".to_string()));
+        let interval = Duration::from_millis(200);
+        let client = client(&server, &cache).with_min_interval(interval);
+
+        let start = Instant::now();
+        client.fetch_input(1).unwrap();
+        client.fetch_input(2).unwrap();
+
+        assert!(start.elapsed() >= interval);
+        assert_eq!(server.requests().len(), 2);
+    }
+}
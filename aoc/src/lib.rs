@@ -0,0 +1,122 @@
+pub mod answers;
+pub mod client;
+#[cfg(test)]
+mod mock;
+pub mod submit;
+pub mod verify;
+
+use std::error::Error;
+
+pub use aoc_core::Part;
+use aoc_core::Solution;
+
+use day_01::Day01;
+use day_02::Day02;
+use day_03::Day03;
+use day_04::Day04;
+use day_05::Day05;
+use day_06::Day06;
+use day_07::Day07;
+use day_08::Day08;
+use day_09::Day09;
+
+/// Days with a solution, in calendar order.
+pub const DAYS: [u8; 9] = [
+    Day01::DAY,
+    Day02::DAY,
+    Day03::DAY,
+    Day04::DAY,
+    Day05::DAY,
+    Day06::DAY,
+    Day07::DAY,
+    Day08::DAY,
+    Day09::DAY,
+];
+
+/// Default location of the puzzle input for `day`, relative to the workspace root.
+pub fn default_input(day: u8) -> String {
+    format!("day-{day:02}/input.txt")
+}
+
+/// Runs the solver for the given day and part.
+pub fn solve(day: u8, part: Part, input: &str) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let result = match day {
+        Day01::DAY => Day01::solve(part, input)?,
+        Day02::DAY => Day02::solve(part, input)?,
+        Day03::DAY => Day03::solve(part, input)?,
+        Day04::DAY => Day04::solve(part, input)?,
+        Day05::DAY => Day05::solve(part, input)?,
+        Day06::DAY => Day06::solve(part, input)?,
+        Day07::DAY => Day07::solve(part, input)?,
+        Day08::DAY => Day08::solve(part, input)?,
+        Day09::DAY => Day09::solve(part, input)?,
+        _ => return Err(format!("day {day} is not solved yet").into()),
+    };
+
+    Ok(result)
+}
+
+/// Days whose solvers can skip invalid steps, see [`solve_lenient`].
+pub const LENIENT_DAYS: [u8; 1] = [Day05::DAY];
+
+/// Runs the solver for the given day and part, skipping invalid steps of the input instead of
+/// failing on them; each skipped step is returned as a warning.
+///
+/// Only the [`LENIENT_DAYS`], whose inputs are processed step by step, have such a mode.
+pub fn solve_lenient(
+    day: u8,
+    part: Part,
+    input: &str,
+) -> Result<(String, Vec<String>), Box<dyn Error + Send + Sync>> {
+    let (answer, skipped) = match (day, part) {
+        (Day05::DAY, Part::One) => day_05::part1_lenient(input)?,
+        (Day05::DAY, Part::Two) => day_05::part2_lenient(input)?,
+        _ => return Err(format!("day {day} has no lenient mode").into()),
+    };
+
+    Ok((answer, skipped.iter().map(ToString::to_string).collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solve_dispatches_to_day() {
+        let result = solve(6, Part::Two, "mjqjpqmgbljsphdztnvjfqwrcgsmlb");
+        assert_eq!(result.unwrap(), "19");
+    }
+
+    #[test]
+    fn solve_lenient_skips_invalid_moves() {
+        let input = day_05::EXAMPLE.replace("move 2 from 2 to 1", "move 2 from 2 to 4");
+        let (answer, warnings) = solve_lenient(5, Part::One, &input).unwrap();
+        assert_eq!(answer, "CZ");
+        assert_eq!(
+            warnings,
+            [
+                "skipped: move on line 8 uses stack 4, but stacks are numbered 1 to 3",
+                "skipped: move on line 9 takes crates from empty stack 1",
+            ]
+        );
+
+        assert_eq!(
+            solve_lenient(1, Part::One, "1").unwrap_err().to_string(),
+            "day 1 has no lenient mode"
+        );
+    }
+
+    #[test]
+    fn solve_unknown_day() {
+        assert!(solve(25, Part::One, "").is_err());
+    }
+
+    #[test]
+    fn solve_reports_day_errors() {
+        let error = solve(9, Part::One, "R 4\nX 3").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid direction \"X\" at line 2, column 1"
+        );
+    }
+}
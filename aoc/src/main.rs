@@ -0,0 +1,384 @@
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use aoc::answers::{self, Answers};
+use aoc::client::{self, Client};
+use aoc::submit::{self, History, Outcome, HISTORY_FILE};
+use aoc::verify::{verify, Verdict};
+use aoc::{default_input, solve, solve_lenient, Part, DAYS, LENIENT_DAYS};
+use aoc_core::input;
+use clap::error::ErrorKind;
+use clap::{Args, CommandFactory, Parser, Subcommand};
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::EnvFilter;
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2022 runner")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Log solver spans and events to stderr, at `info` or the given filter (e.g. `--trace=day_09=debug`)
+    #[arg(
+        long,
+        global = true,
+        value_name = "FILTER",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "info"
+    )]
+    trace: Option<String>,
+}
+
+impl Cli {
+    /// Parses `args`, also rejecting combinations of arguments clap cannot express.
+    fn try_parse_checked<I, T>(args: I) -> Result<Self, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let cli = Cli::try_parse_from(args)?;
+
+        if let Command::Run {
+            day, lenient: true, ..
+        } = cli.command
+        {
+            if !LENIENT_DAYS.contains(&day) {
+                let days = LENIENT_DAYS.map(|day| day.to_string()).join(", ");
+                return Err(Cli::command().error(
+                    ErrorKind::ArgumentConflict,
+                    format!("--lenient is only supported for day {days}, not day {day}"),
+                ));
+            }
+        }
+
+        Ok(cli)
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve one part of a day's puzzle
+    Run {
+        #[arg(short, long)]
+        day: u8,
+        #[arg(short, long, value_parser = parse_part)]
+        part: Part,
+        /// Puzzle inputs, `-` for stdin; defaults to `day-XX/input.txt`
+        #[arg(short, long = "input", num_args = 1..)]
+        inputs: Vec<String>,
+        /// Skip invalid steps instead of failing, reporting each on stderr (day 5 only)
+        #[arg(long)]
+        lenient: bool,
+    },
+    /// Check every day's answers against the answer registry
+    Verify {
+        /// Only verify this day
+        #[arg(short, long)]
+        day: Option<u8>,
+        /// Answer registry
+        #[arg(short, long, default_value = answers::DEFAULT_PATH)]
+        answers: PathBuf,
+        /// Store answers for inputs the registry does not know yet
+        #[arg(long)]
+        record: bool,
+    },
+    /// Download a day's puzzle input into the cache and print its path
+    Fetch {
+        #[arg(short, long)]
+        day: u8,
+        #[command(flatten)]
+        remote: Remote,
+    },
+    /// Solve one part of a day's puzzle and submit the answer
+    Submit {
+        #[arg(short, long)]
+        day: u8,
+        #[arg(short, long, value_parser = parse_part)]
+        part: Part,
+        /// Puzzle input, `-` for stdin; defaults to the fetched input
+        #[arg(short, long)]
+        input: Option<String>,
+        /// Submission history; defaults to `submissions.toml` in the cache directory
+        #[arg(long)]
+        history: Option<PathBuf>,
+        #[command(flatten)]
+        remote: Remote,
+    },
+}
+
+/// Connection to the puzzle server.
+#[derive(Args)]
+struct Remote {
+    /// Value of the `session` cookie of a logged-in browser
+    #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+    session: Option<String>,
+    /// Directory caching downloaded inputs; defaults to the per-user cache directory
+    #[arg(long, env = "AOC_CACHE_DIR")]
+    cache_dir: Option<PathBuf>,
+    #[arg(long, env = "AOC_BASE_URL", default_value = client::DEFAULT_BASE_URL, hide = true)]
+    base_url: String,
+}
+
+impl Remote {
+    fn client(self) -> Result<Client, String> {
+        let cache_dir = self
+            .cache_dir
+            .or_else(client::default_cache_dir)
+            .ok_or("no per-user cache directory, pass --cache-dir or set AOC_CACHE_DIR")?;
+
+        Ok(Client::new(self.session, cache_dir).with_base_url(&self.base_url))
+    }
+}
+
+fn parse_part(s: &str) -> Result<Part, String> {
+    let value = s.parse::<u8>().map_err(|e| e.to_string())?;
+    Part::try_from(value)
+}
+
+fn run_verify(day: Option<u8>, path: PathBuf, record: bool) -> ExitCode {
+    let mut answers = match Answers::load(&path) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("cannot load {}: {e}", path.display());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let days = DAYS.into_iter().filter(|d| day.is_none_or(|day| day == *d));
+    let mut failed = false;
+
+    println!(
+        "{:>3}  {:>4}  {:<20}  {:<20}  status",
+        "day", "part", "expected", "actual"
+    );
+
+    for day in days {
+        let input_path = default_input(day);
+        let Ok(input) = fs::read_to_string(&input_path) else {
+            println!("{day:>3}  {:>4}  {input_path} is missing", "-");
+            continue;
+        };
+
+        for part in [Part::One, Part::Two] {
+            let check = verify(day, part, &input, &answers);
+            let verdict = check.verdict();
+            let actual = match &check.actual {
+                Ok(actual) => actual.clone(),
+                Err(e) => e.clone(),
+            };
+
+            println!(
+                "{day:>3}  {part:>4}  {:<20}  {actual:<20}  {verdict}",
+                check.expected.as_deref().unwrap_or("-"),
+            );
+
+            match (verdict, &check.actual) {
+                (Verdict::Fail | Verdict::Error, _) => failed = true,
+                (Verdict::Unknown, Ok(actual)) if record => {
+                    answers.insert(day, part, &check.input, actual)
+                }
+                _ => (),
+            }
+        }
+    }
+
+    if record {
+        if let Err(e) = answers.save(&path) {
+            eprintln!("cannot save {}: {e}", path.display());
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn run_fetch(day: u8, remote: Remote) -> ExitCode {
+    let result = remote
+        .client()
+        .and_then(|client| client.fetch_input(day).map_err(|e| e.to_string()));
+
+    match result {
+        Ok(path) => {
+            println!("{}", path.display());
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("cannot fetch day {day}: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run_submit(
+    day: u8,
+    part: Part,
+    input: Option<String>,
+    history: Option<PathBuf>,
+    remote: Remote,
+) -> ExitCode {
+    let client = match remote.client() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("cannot submit day {day}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let input_path = match input {
+        Some(path) => path,
+        None => match client.fetch_input(day) {
+            Ok(path) => path.display().to_string(),
+            Err(e) => {
+                eprintln!("cannot fetch day {day}: {e}");
+                return ExitCode::FAILURE;
+            }
+        },
+    };
+
+    let answer = match input::read(&input_path)
+        .map_err(|e| e.to_string())
+        .and_then(|input| solve(day, part, &input).map_err(|e| e.to_string()))
+    {
+        Ok(answer) => answer,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let history_path = history.unwrap_or_else(|| client.cache_dir().join(HISTORY_FILE));
+    let mut history = match History::load(&history_path) {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("cannot load {}: {e}", history_path.display());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    println!("day {day} part {part}: submitting {answer}");
+    let result = submit::submit(&client, &mut history, day, part, &answer);
+
+    if let Err(e) = history.save(&history_path) {
+        eprintln!("cannot save {}: {e}", history_path.display());
+        return ExitCode::FAILURE;
+    }
+
+    match result {
+        Ok(reply) => {
+            match reply.wait {
+                Some(wait) => println!("{}, wait {wait}s before the next answer", reply.outcome),
+                None => println!("{}", reply.outcome),
+            }
+
+            if reply.outcome == Outcome::Correct {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn init_tracing(filter: &str) -> Result<(), String> {
+    let filter = EnvFilter::try_new(filter).map_err(|e| format!("invalid trace filter: {e}"))?;
+
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_writer(io::stderr)
+        .init();
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::try_parse_checked(env::args_os()).unwrap_or_else(|e| e.exit());
+
+    if let Some(filter) = &cli.trace {
+        if let Err(e) = init_tracing(filter) {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    }
+
+    match cli.command {
+        Command::Run {
+            day,
+            part,
+            inputs,
+            lenient,
+        } => {
+            let paths = input::paths(inputs, &default_input(day));
+            if lenient {
+                input::solve_all_with_warnings(&paths, |input| solve_lenient(day, part, input))
+            } else {
+                input::solve_all(&paths, |input| solve(day, part, input))
+            }
+        }
+        Command::Verify {
+            day,
+            answers,
+            record,
+        } => run_verify(day, answers, record),
+        Command::Fetch { day, remote } => run_fetch(day, remote),
+        Command::Submit {
+            day,
+            part,
+            input,
+            history,
+            remote,
+        } => run_submit(day, part, input, history, remote),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+        Cli::try_parse_checked([&["aoc"], args].concat())
+    }
+
+    #[test]
+    fn trace_takes_its_filter_only_after_equals() {
+        let cli = parse(&["--trace", "run", "--day", "2", "--part", "1"]).unwrap();
+        assert_eq!(cli.trace.as_deref(), Some("info"));
+        assert!(matches!(cli.command, Command::Run { day: 2, .. }));
+
+        let cli = parse(&["run", "-d", "2", "-p", "1", "--trace", "-i", "input.txt"]).unwrap();
+        assert_eq!(cli.trace.as_deref(), Some("info"));
+        assert!(matches!(cli.command, Command::Run { ref inputs, .. } if inputs == &["input.txt"]));
+
+        let cli = parse(&["--trace=day_09=debug", "run", "-d", "9", "-p", "2"]).unwrap();
+        assert_eq!(cli.trace.as_deref(), Some("day_09=debug"));
+
+        assert!(parse(&["run", "-d", "2", "-p", "1"])
+            .unwrap()
+            .trace
+            .is_none());
+    }
+
+    #[test]
+    fn run_accepts_lenient_only_for_lenient_days() {
+        let cli = parse(&["run", "-d", "5", "-p", "1", "--lenient"]).unwrap();
+        assert!(matches!(cli.command, Command::Run { lenient: true, .. }));
+
+        let error = parse(&["run", "-d", "6", "-p", "1", "--lenient"])
+            .err()
+            .expect("day 6 has no lenient mode");
+        assert_eq!(error.kind(), ErrorKind::ArgumentConflict);
+        assert!(error.to_string().contains("not day 6"));
+    }
+}
@@ -0,0 +1,94 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::thread::JoinHandle;
+
+use tiny_http::{Header, Response, Server};
+
+/// Request as seen by the [`MockServer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recorded {
+    pub method: String,
+    pub path: String,
+    pub cookie: Option<String>,
+    pub body: String,
+}
+
+/// Local stand-in for the puzzle server, answering with a handler on a random port.
+pub struct MockServer {
+    pub url: String,
+    pub requests: Arc<Mutex<Vec<Recorded>>>,
+    server: Arc<Server>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    pub fn start(handler: impl Fn(&Recorded) -> (u16, String) + Send + 'static) -> Self {
+        let server = Arc::new(Server::http("127.0.0.1:0").unwrap());
+        let port = server.server_addr().to_ip().unwrap().port();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let handle = {
+            let server = server.clone();
+            let requests = requests.clone();
+
+            thread::spawn(move || {
+                for mut request in server.incoming_requests() {
+                    let mut body = String::new();
+                    request.as_reader().read_to_string(&mut body).unwrap();
+
+                    let recorded = Recorded {
+                        method: request.method().to_string(),
+                        path: request.url().to_string(),
+                        cookie: request
+                            .headers()
+                            .iter()
+                            .find(|header| header.field.equiv("Cookie"))
+                            .map(|header| header.value.to_string()),
+                        body,
+                    };
+
+                    let (status, content) = handler(&recorded);
+                    requests.lock().unwrap().push(recorded);
+
+                    let content_type = Header::from_bytes("Content-Type", "text/html").unwrap();
+                    let response = Response::from_string(content)
+                        .with_status_code(status)
+                        .with_header(content_type);
+                    request.respond(response).unwrap();
+                }
+            })
+        };
+
+        MockServer {
+            url: format!("http://127.0.0.1:{port}"),
+            requests,
+            server,
+            handle: Some(handle),
+        }
+    }
+
+    pub fn requests(&self) -> Vec<Recorded> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(handle) = self.handle.take() {
+            handle.join().unwrap();
+        }
+    }
+}
+
+/// Fresh, empty scratch directory for a test.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aoc-{}-{name}", process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
@@ -0,0 +1,493 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::client::{self, Client, ClientError, YEAR};
+use crate::Part;
+
+/// File in the cache directory recording every submission.
+pub const HISTORY_FILE: &str = "submissions.toml";
+
+/// Verdict of the server on a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction.
+    Wrong,
+    /// Rejected unseen because the previous submission was too recent.
+    TooRecent,
+    /// The part is locked or already solved.
+    WrongLevel,
+}
+
+impl Outcome {
+    /// Whether the server judged the answer itself, as opposed to refusing to look at it.
+    pub fn is_verdict(self) -> bool {
+        !matches!(self, Outcome::TooRecent | Outcome::WrongLevel)
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too high",
+            Outcome::TooLow => "too low",
+            Outcome::Wrong => "wrong",
+            Outcome::TooRecent => "submitted too recently",
+            Outcome::WrongLevel => "part is locked or already solved",
+        };
+
+        f.pad(s)
+    }
+}
+
+/// Parsed server response to a submission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reply {
+    pub outcome: Outcome,
+    /// Seconds the server asks to wait before the next submission.
+    pub wait: Option<u64>,
+}
+
+impl Reply {
+    /// Reads the reply out of the HTML page the server answers a submission with.
+    pub fn parse(page: &str) -> Option<Reply> {
+        let text = article_text(page);
+
+        let outcome = if text.contains("That's the right answer") {
+            Outcome::Correct
+        } else if text.contains("too high") {
+            Outcome::TooHigh
+        } else if text.contains("too low") {
+            Outcome::TooLow
+        } else if text.contains("That's not the right answer") {
+            Outcome::Wrong
+        } else if text.contains("You gave an answer too recently") {
+            Outcome::TooRecent
+        } else if text.contains("You don't seem to be solving the right level") {
+            Outcome::WrongLevel
+        } else {
+            return None;
+        };
+
+        Some(Reply {
+            outcome,
+            wait: parse_wait(&text),
+        })
+    }
+}
+
+/// Text of the page's `<article>`, or of the whole page if it has none, without tags.
+fn article_text(page: &str) -> String {
+    let article = page
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(page, |(article, _)| article);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Understands "You have 1m 5s left to wait" and "Please wait 5 minutes before trying again".
+fn parse_wait(text: &str) -> Option<u64> {
+    if let Some((_, rest)) = text.split_once("You have ") {
+        let (left, _) = rest.split_once(" left to wait")?;
+        return left.split_whitespace().try_fold(0, |total, part| {
+            let (value, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let value = value.parse::<u64>().ok()?;
+            match unit {
+                "h" => Some(total + value * 3600),
+                "m" => Some(total + value * 60),
+                "s" => Some(total + value),
+                _ => None,
+            }
+        });
+    }
+
+    let (_, rest) = text.split_once("wait ")?;
+    let (amount, rest) = rest.split_once(' ')?;
+    let minutes = match amount {
+        "one" => 1,
+        amount => amount.parse().ok()?,
+    };
+    rest.starts_with("minute").then_some(minutes * 60)
+}
+
+/// One submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// Seconds since the Unix epoch.
+    pub time: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait: Option<u64>,
+}
+
+/// Reason not to send an answer to the server.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum Refusal {
+    #[error("already solved with {answer}")]
+    AlreadySolved { answer: String },
+    #[error("{answer} was already rejected")]
+    KnownWrong { answer: String },
+    #[error("{answer} is not below {bound}, which is too high")]
+    AboveBound { answer: String, bound: String },
+    #[error("{answer} is not above {bound}, which is too low")]
+    BelowBound { answer: String, bound: String },
+    #[error("the server asked to wait, {seconds}s left")]
+    Cooldown { seconds: u64 },
+}
+
+/// Every answer submitted so far, stored as TOML.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct History {
+    #[serde(default, rename = "attempt")]
+    attempts: Vec<Attempt>,
+}
+
+impl History {
+    /// Reads the history at `path`, treating a missing file as empty.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(toml::from_str(&content)?),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(History::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error + Send + Sync>> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    pub fn attempts(&self, day: u8, part: Part) -> impl Iterator<Item = &Attempt> {
+        self.attempts
+            .iter()
+            .filter(move |attempt| attempt.day == day && attempt.part == part as u8)
+    }
+
+    pub fn record(&mut self, day: u8, part: Part, answer: &str, reply: Reply, time: u64) {
+        self.attempts.push(Attempt {
+            day,
+            part: part as u8,
+            answer: answer.to_string(),
+            outcome: reply.outcome,
+            time,
+            wait: reply.wait,
+        });
+    }
+
+    /// Checks `answer` against earlier attempts at `time`, before bothering the server.
+    pub fn check(&self, day: u8, part: Part, answer: &str, time: u64) -> Result<(), Refusal> {
+        if let Some(seconds) = self
+            .attempts
+            .iter()
+            .filter_map(|attempt| Some(attempt.time + attempt.wait?))
+            .max()
+            .and_then(|until| until.checked_sub(time))
+            .filter(|seconds| *seconds > 0)
+        {
+            return Err(Refusal::Cooldown { seconds });
+        }
+
+        let value = answer.parse::<i64>().ok();
+
+        for attempt in self.attempts(day, part) {
+            let bound = attempt.answer.parse::<i64>().ok();
+            let refusal = match attempt.outcome {
+                Outcome::Correct => Refusal::AlreadySolved {
+                    answer: attempt.answer.clone(),
+                },
+                outcome if outcome.is_verdict() && attempt.answer == answer => {
+                    Refusal::KnownWrong {
+                        answer: answer.to_string(),
+                    }
+                }
+                Outcome::TooHigh if value.zip(bound).is_some_and(|(v, b)| v >= b) => {
+                    Refusal::AboveBound {
+                        answer: answer.to_string(),
+                        bound: attempt.answer.clone(),
+                    }
+                }
+                Outcome::TooLow if value.zip(bound).is_some_and(|(v, b)| v <= b) => {
+                    Refusal::BelowBound {
+                        answer: answer.to_string(),
+                        bound: attempt.answer.clone(),
+                    }
+                }
+                _ => continue,
+            };
+
+            return Err(refusal);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SubmitError {
+    #[error("not submitting: {0}")]
+    Refused(#[from] Refusal),
+    #[error(transparent)]
+    Client(#[from] ClientError),
+}
+
+/// Submits `answer` unless `history` already rules it out, and records the server's reply.
+pub fn submit(
+    client: &Client,
+    history: &mut History,
+    day: u8,
+    part: Part,
+    answer: &str,
+) -> Result<Reply, SubmitError> {
+    history.check(day, part, answer, client::now().as_secs())?;
+
+    let level = (part as u8).to_string();
+    let page = client.post(
+        &format!("/{YEAR}/day/{day}/answer"),
+        &[("level", &level), ("answer", answer)],
+    )?;
+    let reply = Reply::parse(&page).ok_or_else(|| {
+        let text = article_text(&page);
+        ClientError::UnexpectedResponse(text.chars().take(200).collect())
+    })?;
+
+    history.record(day, part, answer, reply, client::now().as_secs());
+    Ok(reply)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::mock::{temp_dir, MockServer};
+
+    fn page(text: &str) -> String {
+        format!("<html><body><main><article><p>{text}</p></article></main></body></html>")
+    }
+
+    fn reply(outcome: Outcome) -> Reply {
+        Reply {
+            outcome,
+            wait: None,
+        }
+    }
+
+    #[test]
+    fn parse_replies() {
+        let correct = page("That's the right answer!  You are <span>one gold star</span> closer.");
+        assert_eq!(Reply::parse(&correct), Some(reply(Outcome::Correct)));
+
+        let too_high = page(
+            "That's not the right answer; your answer is too high.  \
+             Please wait one minute before trying again.",
+        );
+        assert_eq!(
+            Reply::parse(&too_high),
+            Some(Reply {
+                outcome: Outcome::TooHigh,
+                wait: Some(60)
+            })
+        );
+
+        let too_low = page(
+            "That's not the right answer; your answer is too low.  \
+             Please wait 5 minutes before trying again.",
+        );
+        assert_eq!(
+            Reply::parse(&too_low),
+            Some(Reply {
+                outcome: Outcome::TooLow,
+                wait: Some(300)
+            })
+        );
+
+        let wrong =
+            page("That's not the right answer.  Please wait one minute before trying again.");
+        assert_eq!(Reply::parse(&wrong).unwrap().outcome, Outcome::Wrong);
+
+        let too_recent = page(
+            "You gave an answer too recently; you have to wait after submitting an answer \
+             before trying again.  You have 1m 5s left to wait.",
+        );
+        assert_eq!(
+            Reply::parse(&too_recent),
+            Some(Reply {
+                outcome: Outcome::TooRecent,
+                wait: Some(65)
+            })
+        );
+
+        let level =
+            page("You don't seem to be solving the right level.  Did you already complete it?");
+        assert_eq!(Reply::parse(&level), Some(reply(Outcome::WrongLevel)));
+
+        assert_eq!(Reply::parse(&page("Something else entirely.")), None);
+    }
+
+    #[test]
+    fn check_refuses_known_answers() {
+        let mut history = History::default();
+        history.record(1, Part::One, "50", reply(Outcome::TooHigh), 0);
+        history.record(1, Part::One, "10", reply(Outcome::TooLow), 0);
+        history.record(1, Part::One, "30", reply(Outcome::Wrong), 0);
+        history.record(1, Part::Two, "abc", reply(Outcome::TooRecent), 0);
+
+        assert!(history.check(1, Part::One, "20", 0).is_ok());
+        assert!(history.check(1, Part::Two, "abc", 0).is_ok());
+        assert!(history.check(2, Part::One, "30", 0).is_ok());
+        assert_eq!(
+            history.check(1, Part::One, "30", 0),
+            Err(Refusal::KnownWrong {
+                answer: "30".to_string()
+            })
+        );
+        assert_eq!(
+            history.check(1, Part::One, "60", 0),
+            Err(Refusal::AboveBound {
+                answer: "60".to_string(),
+                bound: "50".to_string()
+            })
+        );
+        assert_eq!(
+            history.check(1, Part::One, "10", 0),
+            Err(Refusal::KnownWrong {
+                answer: "10".to_string()
+            })
+        );
+        assert_eq!(
+            history.check(1, Part::One, "5", 0),
+            Err(Refusal::BelowBound {
+                answer: "5".to_string(),
+                bound: "10".to_string()
+            })
+        );
+
+        history.record(1, Part::One, "25", reply(Outcome::Correct), 0);
+        assert_eq!(
+            history.check(1, Part::One, "20", 0),
+            Err(Refusal::AlreadySolved {
+                answer: "25".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn check_respects_cooldown() {
+        let mut history = History::default();
+        let wait = Reply {
+            outcome: Outcome::Wrong,
+            wait: Some(60),
+        };
+        history.record(1, Part::One, "30", wait, 1000);
+
+        assert_eq!(
+            history.check(2, Part::Two, "1", 1045),
+            Err(Refusal::Cooldown { seconds: 15 })
+        );
+        assert!(history.check(2, Part::Two, "1", 1060).is_ok());
+    }
+
+    #[test]
+    fn history_round_trips() {
+        let path = temp_dir("history").join(HISTORY_FILE);
+        let mut history = History::default();
+        history.record(3, Part::Two, "2499", reply(Outcome::Correct), 1670000000);
+        let wait = Reply {
+            outcome: Outcome::TooLow,
+            wait: Some(60),
+        };
+        history.record(4, Part::One, "12", wait, 1670000100);
+
+        history.save(&path).unwrap();
+        assert_eq!(History::load(&path).unwrap(), history);
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .contains("outcome = \"too-low\""));
+    }
+
+    #[test]
+    fn submit_posts_and_records() {
+        let cache = temp_dir("submit");
+        let server = MockServer::start(|request| match request.body.as_str() {
+            "level=1&answer=24000" => (200, page("That's the right answer!")),
+            _ => (
+                200,
+                page("That's not the right answer; your answer is too low."),
+            ),
+        });
+        let client = Client::new(Some("53cr3t".to_string()), &cache)
+            .with_base_url(&server.url)
+            .with_min_interval(Duration::ZERO);
+        let mut history = History::default();
+
+        let reply = submit(&client, &mut history, 1, Part::Two, "100").unwrap();
+        assert_eq!(reply.outcome, Outcome::TooLow);
+
+        let refused = submit(&client, &mut history, 1, Part::Two, "99").unwrap_err();
+        assert_eq!(
+            refused.to_string(),
+            "not submitting: 99 is not above 100, which is too low"
+        );
+
+        let reply = submit(&client, &mut history, 1, Part::One, "24000").unwrap();
+        assert_eq!(reply.outcome, Outcome::Correct);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2022/day/1/answer");
+        assert_eq!(requests[0].cookie.as_deref(), Some("session=53cr3t"));
+        assert_eq!(requests[0].body, "level=2&answer=100");
+
+        let answers: Vec<_> = history
+            .attempts(1, Part::One)
+            .map(|attempt| (attempt.answer.as_str(), attempt.outcome))
+            .collect();
+        assert_eq!(answers, [("24000", Outcome::Correct)]);
+    }
+
+    #[test]
+    fn submit_rejects_unknown_pages() {
+        let cache = temp_dir("submit-unknown");
+        let server = MockServer::start(|_| (200, page("Maintenance in progress.")));
+        let client = Client::new(Some("53cr3t".to_string()), &cache)
+            .with_base_url(&server.url)
+            .with_min_interval(Duration::ZERO);
+        let mut history = History::default();
+
+        let error = submit(&client, &mut history, 1, Part::One, "1").unwrap_err();
+        assert!(matches!(
+            error,
+            SubmitError::Client(ClientError::UnexpectedResponse(_))
+        ));
+        assert_eq!(history.attempts(1, Part::One).count(), 0);
+    }
+}
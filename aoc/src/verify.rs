@@ -0,0 +1,94 @@
+use std::fmt;
+
+use crate::answers::{input_hash, Answers};
+use crate::{solve, Part};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    /// Matches the recorded answer.
+    Pass,
+    /// Differs from the recorded answer.
+    Fail,
+    /// No answer is recorded for this input yet.
+    Unknown,
+    /// The solver returned an error.
+    Error,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verdict = match self {
+            Verdict::Pass => "pass",
+            Verdict::Fail => "FAIL",
+            Verdict::Unknown => "unknown",
+            Verdict::Error => "ERROR",
+        };
+
+        // Padding is forwarded so verdicts line up in tables.
+        f.pad(verdict)
+    }
+}
+
+/// Result of running one part against the answer registry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub day: u8,
+    pub part: Part,
+    pub input: String,
+    pub expected: Option<String>,
+    pub actual: Result<String, String>,
+}
+
+impl Check {
+    pub fn verdict(&self) -> Verdict {
+        match (&self.expected, &self.actual) {
+            (_, Err(_)) => Verdict::Error,
+            (None, Ok(_)) => Verdict::Unknown,
+            (Some(expected), Ok(actual)) if expected == actual => Verdict::Pass,
+            (Some(_), Ok(_)) => Verdict::Fail,
+        }
+    }
+}
+
+/// Solves `part` of `day` and compares the result with the recorded answer for `input`.
+pub fn verify(day: u8, part: Part, input: &str, answers: &Answers) -> Check {
+    let hash = input_hash(input);
+
+    Check {
+        day,
+        part,
+        expected: answers.get(day, part, &hash).map(str::to_string),
+        actual: solve(day, part, input).map_err(|e| e.to_string()),
+        input: hash,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verdicts() {
+        let mut answers = Answers::default();
+        let input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+        answers.insert(6, Part::One, &input_hash(input), "7");
+        answers.insert(6, Part::Two, &input_hash(input), "20");
+
+        assert_eq!(
+            verify(6, Part::One, input, &answers).verdict(),
+            Verdict::Pass
+        );
+        assert_eq!(
+            verify(6, Part::Two, input, &answers).verdict(),
+            Verdict::Fail
+        );
+        assert_eq!(
+            verify(6, Part::One, "abcd", &answers).verdict(),
+            Verdict::Unknown
+        );
+        assert_eq!(
+            verify(6, Part::One, "aaaa", &answers).verdict(),
+            Verdict::Error
+        );
+    }
+}
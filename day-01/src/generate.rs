@@ -0,0 +1,33 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Calorie list with `elves` inventories of 1 to 15 items each.
+pub fn input(seed: u64, elves: usize) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    (0..elves)
+        .map(|_| {
+            let items = rng.gen_range(1..=15);
+
+            (0..items)
+                .map(|_| rng.gen_range(1000..=60000).to_string())
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn generated_input_is_valid() {
+        let generated = input(1, 500);
+        assert_eq!(generated, input(1, 500));
+        assert!(part1(&generated).is_ok());
+        assert!(part2(&generated).is_ok());
+    }
+}
@@ -1,40 +1,28 @@
-pub fn part1(input: &str) -> String {
-    let result = input
-        .split("\n\n")
-        .map(|load| {
-            println!("{:}", load);
-            load.lines()
-                .map(|item| item.parse::<u32>().unwrap())
-                .sum::<u32>()
-        })
-        .max()
-        .unwrap();
-
-    result.to_string()
-}
+pub mod generate;
+#[cfg(test)]
+mod reference;
+pub mod stream;
 
-pub fn part2(input: &str) -> String {
-    let mut ranking = input
-        .split("\n\n")
-        .map(|load| {
-            load.lines()
-                .map(|item| item.parse::<u32>().unwrap())
-                .sum::<u32>()
-        })
-        .collect::<Vec<_>>();
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
 
-    ranking.sort_by(|left, right| right.cmp(left));
+use aoc_core::{Part, Position, Solution};
+use thiserror::Error;
 
-    let result = ranking.iter().take(3).sum::<u32>();
+use crate::stream::Totals;
 
-    result.to_string()
+#[derive(Debug, Error, PartialEq)]
+pub enum Error {
+    #[error("invalid calories {token:?} at {position}")]
+    InvalidCalories { position: Position, token: String },
+    #[error("calories of the elf at line {line} overflow")]
+    Overflow { line: usize },
+    #[error("no elves in input")]
+    NoElves,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const INPUT: &str = "1000
+/// Example from the puzzle description.
+pub const EXAMPLE: &str = "1000
 2000
 3000
 
@@ -49,15 +37,95 @@ mod tests {
 
 10000";
 
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Calorie Counting";
+
+    type Input<'a> = Vec<u32>;
+    type Output1 = u32;
+    type Output2 = u32;
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        Totals::new(input.as_bytes()).collect()
+    }
+
+    fn part1(loads: &Self::Input<'_>) -> Result<Self::Output1, Self::Error> {
+        loads.iter().max().copied().ok_or(Error::NoElves)
+    }
+
+    fn part2(loads: &Self::Input<'_>) -> Result<Self::Output2, Self::Error> {
+        if loads.is_empty() {
+            return Err(Error::NoElves);
+        }
+
+        Ok(top_n(loads.iter().copied(), 3).iter().sum())
+    }
+}
+
+/// The `k` largest items, largest first, in a single pass keeping only `k` of them.
+pub fn top_n<T: Ord>(items: impl IntoIterator<Item = T>, k: usize) -> Vec<T> {
+    if k == 0 {
+        return Vec::new();
+    }
+
+    // Grows with the items seen rather than with `k`, which may far exceed their number.
+    let mut top = BinaryHeap::new();
+
+    for item in items {
+        top.push(Reverse(item));
+        if top.len() > k {
+            top.pop();
+        }
+    }
+
+    top.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(item)| item)
+        .collect()
+}
+
+pub fn part1(input: &str) -> Result<String, Error> {
+    Day01::solve(Part::One, input)
+}
+
+pub fn part2(input: &str) -> Result<String, Error> {
+    Day01::solve(Part::Two, input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn part1_works() {
-        let result = part1(INPUT);
+        let result = part1(EXAMPLE).unwrap();
         assert_eq!(result, "24000");
     }
 
     #[test]
     fn part2_works() {
-        let result = part2(INPUT);
+        let result = part2(EXAMPLE).unwrap();
         assert_eq!(result, "45000");
     }
+
+    #[test]
+    fn top_n_with_more_slots_than_items() {
+        assert_eq!(top_n([1, 3, 2], 1 << 40), [3, 2, 1]);
+        assert_eq!(top_n([1, 3, 2], usize::MAX), [3, 2, 1]);
+    }
+
+    #[test]
+    fn invalid_calories_are_located() {
+        let result = part1("1000\n\n20x0\n3000");
+        assert_eq!(
+            result,
+            Err(Error::InvalidCalories {
+                position: Position { line: 3, column: 1 },
+                token: "20x0".to_string(),
+            })
+        );
+    }
 }
@@ -0,0 +1,74 @@
+use proptest::prelude::*;
+
+use std::io::BufReader;
+
+use crate::stream::{ReadTotals, Totals};
+use crate::{generate, part1, part2, top_n};
+
+/// Per-elf totals, summed line by line.
+fn totals(input: &str) -> Vec<u32> {
+    let mut totals = vec![0];
+
+    for line in input.lines() {
+        if line.is_empty() {
+            totals.push(0);
+        } else {
+            *totals.last_mut().unwrap() += line.parse::<u32>().unwrap();
+        }
+    }
+
+    totals
+}
+
+fn top(input: &str, count: usize) -> u32 {
+    let mut totals = totals(input);
+    let mut sum = 0;
+
+    for _ in 0..count.min(totals.len()) {
+        let (idx, max) = totals
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, total)| **total)
+            .unwrap();
+        sum += max;
+        totals.remove(idx);
+    }
+
+    sum
+}
+
+proptest! {
+    #[test]
+    fn part1_matches_reference(seed in any::<u64>(), elves in 1..200usize) {
+        let input = generate::input(seed, elves);
+        prop_assert_eq!(part1(&input).unwrap(), top(&input, 1).to_string());
+    }
+
+    #[test]
+    fn part2_matches_reference(seed in any::<u64>(), elves in 1..200usize) {
+        let input = generate::input(seed, elves);
+        prop_assert_eq!(part2(&input).unwrap(), top(&input, 3).to_string());
+    }
+
+    #[test]
+    fn streams_match_reference(seed in any::<u64>(), elves in 1..200usize, capacity in 1..64usize) {
+        let input = generate::input(seed, elves);
+        let expected = totals(&input);
+
+        let totals: Vec<_> = Totals::new(input.as_bytes()).map(Result::unwrap).collect();
+        prop_assert_eq!(&totals, &expected);
+
+        let reader = BufReader::with_capacity(capacity, input.as_bytes());
+        let read: Vec<_> = ReadTotals::new(reader).map(Result::unwrap).collect();
+        prop_assert_eq!(&read, &expected);
+    }
+
+    #[test]
+    fn top_n_matches_reference(seed in any::<u64>(), elves in 1..200usize, k in 0..20usize) {
+        let input = generate::input(seed, elves);
+        let top = top_n(totals(&input), k);
+        prop_assert_eq!(top.len(), k.min(elves));
+        prop_assert_eq!(top.iter().sum::<u32>(), self::top(&input, k));
+        prop_assert!(top.windows(2).all(|pair| pair[0] >= pair[1]));
+    }
+}
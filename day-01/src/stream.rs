@@ -0,0 +1,234 @@
+//! Streaming calorie parser yielding one total per elf.
+//!
+//! Neither iterator keeps more than the elf being summed: [`Totals`] walks a byte slice in
+//! place and [`ReadTotals`] reuses a single line buffer, so logs of any size can be processed.
+
+use std::io;
+use std::io::BufRead;
+
+use aoc_core::Position;
+
+use crate::{top_n, Error};
+
+/// Reading a calorie log failed, or it is malformed.
+#[derive(Debug, thiserror::Error)]
+pub enum ReadError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Parse(#[from] Error),
+}
+
+/// Sums calories line by line; blank lines separate elves.
+#[derive(Debug, Default)]
+struct Group {
+    /// 1-based number of the last line seen.
+    line: usize,
+    total: Option<u32>,
+}
+
+impl Group {
+    /// Adds one line, yielding the finished elf's total on a blank line.
+    fn push(&mut self, line: &[u8]) -> Option<Result<u32, Error>> {
+        self.line += 1;
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+
+        if line.is_empty() {
+            return self.total.take().map(Ok);
+        }
+
+        let calories = match parse_calories(line) {
+            Some(calories) => calories,
+            None => return Some(Err(self.invalid(line))),
+        };
+
+        match self.total.unwrap_or(0).checked_add(calories) {
+            Some(total) => {
+                self.total = Some(total);
+                None
+            }
+            None => Some(Err(Error::Overflow { line: self.line })),
+        }
+    }
+
+    /// Yields the last elf's total at the end of the input.
+    fn finish(&mut self) -> Option<Result<u32, Error>> {
+        self.total.take().map(Ok)
+    }
+
+    fn invalid(&self, line: &[u8]) -> Error {
+        Error::InvalidCalories {
+            position: Position {
+                line: self.line,
+                column: 1,
+            },
+            token: String::from_utf8_lossy(line).into_owned(),
+        }
+    }
+}
+
+fn parse_calories(line: &[u8]) -> Option<u32> {
+    line.iter().try_fold(0_u32, |value, byte| {
+        let digit = (*byte as char).to_digit(10)?;
+        value.checked_mul(10)?.checked_add(digit)
+    })
+}
+
+/// Per-elf totals of a calorie log held in memory.
+#[derive(Debug)]
+pub struct Totals<'a> {
+    rest: Option<&'a [u8]>,
+    group: Group,
+}
+
+impl<'a> Totals<'a> {
+    pub fn new(input: &'a [u8]) -> Self {
+        Totals {
+            rest: Some(input),
+            group: Group::default(),
+        }
+    }
+
+    /// The `k` largest totals, largest first.
+    pub fn top_n(self, k: usize) -> Result<Vec<u32>, Error> {
+        try_top_n(self, k)
+    }
+}
+
+impl Iterator for Totals<'_> {
+    type Item = Result<u32, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(rest) = self.rest {
+            let line = match rest.iter().position(|byte| *byte == b'\n') {
+                Some(end) => {
+                    self.rest = Some(&rest[end + 1..]);
+                    &rest[..end]
+                }
+                None => {
+                    self.rest = None;
+                    rest
+                }
+            };
+
+            if let Some(total) = self.group.push(line) {
+                return Some(total);
+            }
+        }
+
+        self.group.finish()
+    }
+}
+
+/// Per-elf totals of a calorie log read incrementally.
+#[derive(Debug)]
+pub struct ReadTotals<R> {
+    reader: R,
+    line: Vec<u8>,
+    group: Group,
+    done: bool,
+}
+
+impl<R: BufRead> ReadTotals<R> {
+    pub fn new(reader: R) -> Self {
+        ReadTotals {
+            reader,
+            line: Vec::new(),
+            group: Group::default(),
+            done: false,
+        }
+    }
+
+    /// The `k` largest totals, largest first.
+    pub fn top_n(self, k: usize) -> Result<Vec<u32>, ReadError> {
+        try_top_n(self, k)
+    }
+}
+
+impl<R: BufRead> Iterator for ReadTotals<R> {
+    type Item = Result<u32, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            self.line.clear();
+            match self.reader.read_until(b'\n', &mut self.line) {
+                Ok(0) => self.done = true,
+                Ok(_) => {
+                    let line = self.line.strip_suffix(b"\n").unwrap_or(&self.line);
+                    if let Some(total) = self.group.push(line) {
+                        return Some(total.map_err(ReadError::from));
+                    }
+                }
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e.into()));
+                }
+            }
+        }
+
+        self.group
+            .finish()
+            .map(|total| total.map_err(ReadError::from))
+    }
+}
+
+/// [`top_n`] over fallible items, stopping at the first error.
+fn try_top_n<T: Ord, E>(items: impl Iterator<Item = Result<T, E>>, k: usize) -> Result<Vec<T>, E> {
+    let mut error = None;
+    let top = top_n(
+        items.map_while(|item| item.map_err(|e| error = Some(e)).ok()),
+        k,
+    );
+
+    match error {
+        Some(e) => Err(e),
+        None => Ok(top),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufReader;
+
+    use super::*;
+    use crate::EXAMPLE;
+
+    #[test]
+    fn totals_of_example() {
+        let totals: Result<Vec<_>, _> = Totals::new(EXAMPLE.as_bytes()).collect();
+        assert_eq!(totals.unwrap(), [6000, 4000, 11000, 24000, 10000]);
+    }
+
+    #[test]
+    fn read_totals_across_small_buffers() {
+        let input = "1000\r\n2000\r\n\r\n\r\n3000\n";
+        let reader = BufReader::with_capacity(3, input.as_bytes());
+        let totals: Vec<_> = ReadTotals::new(reader).map(Result::unwrap).collect();
+        assert_eq!(totals, [3000, 3000]);
+    }
+
+    #[test]
+    fn top_n_of_stream() {
+        assert_eq!(
+            Totals::new(EXAMPLE.as_bytes()).top_n(2).unwrap(),
+            [24000, 11000]
+        );
+        assert_eq!(
+            ReadTotals::new(EXAMPLE.as_bytes()).top_n(10).unwrap(),
+            [24000, 11000, 10000, 6000, 4000]
+        );
+        assert_eq!(
+            Totals::new(b"1\n\n2".as_slice()).top_n(usize::MAX).unwrap(),
+            [2, 1]
+        );
+    }
+
+    #[test]
+    fn overflow_is_reported() {
+        let input = "4000000000\n4000000000\n";
+        assert_eq!(
+            Totals::new(input.as_bytes()).top_n(1),
+            Err(Error::Overflow { line: 2 })
+        );
+    }
+}
@@ -0,0 +1,32 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+/// Strategy guide with `rounds` lines.
+pub fn input(seed: u64, rounds: usize) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    (0..rounds)
+        .map(|_| {
+            let opponent = ["A", "B", "C"].choose(&mut rng).unwrap();
+            let column = ["X", "Y", "Z"].choose(&mut rng).unwrap();
+
+            format!("{opponent} {column}")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn generated_input_is_valid() {
+        let generated = input(2, 500);
+        assert_eq!(generated, input(2, 500));
+        assert!(part1(&generated).is_ok());
+        assert!(part2(&generated).is_ok());
+    }
+}
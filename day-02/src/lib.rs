@@ -1,113 +1,275 @@
-use std::cmp::Ordering;
+pub mod generate;
+pub mod optimize;
+#[cfg(test)]
+mod reference;
+pub mod ruleset;
+pub mod tournament;
+
 use std::str::FromStr;
+use std::sync::LazyLock;
+
+use aoc_core::{Part, Position, Solution};
+use thiserror::Error;
 
+use crate::ruleset::Ruleset;
+use crate::tournament::{Round, Tournament};
+use crate::Column::{X, Y, Z};
 use crate::Move::{Paper, Rock, Scissors};
 
-#[derive(PartialEq, Copy, Clone)]
-enum Move {
+/// Result of a round from the point of view of one player, valued at its score.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum Outcome {
+    Loss = 0,
+    Draw = 3,
+    Win = 6,
+}
+
+impl Outcome {
+    pub const ALL: [Outcome; 3] = [Outcome::Loss, Outcome::Draw, Outcome::Win];
+
+    pub fn score(self) -> u32 {
+        self as u32
+    }
+
+    /// The same round seen from the other player.
+    pub fn reverse(self) -> Outcome {
+        match self {
+            Outcome::Loss => Outcome::Win,
+            Outcome::Draw => Outcome::Draw,
+            Outcome::Win => Outcome::Loss,
+        }
+    }
+}
+
+/// Shape played in a round, valued at its score.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum Move {
     Rock = 1,
     Paper = 2,
     Scissors = 3,
 }
 
-impl PartialOrd for Move {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        if self == &Scissors && other == &Rock {
-            Some(Ordering::Less)
-        } else if self == &Rock && other == &Scissors {
-            Some(Ordering::Greater)
-        } else {
-            Some((*self as u8).cmp(&(*other as u8)))
-        }
+impl Move {
+    pub const ALL: [Move; 3] = [Rock, Paper, Scissors];
+
+    pub fn score(self) -> u32 {
+        self as u32
+    }
+
+    /// The move this one defeats.
+    pub fn beats(self) -> Move {
+        Move::for_outcome(self, Outcome::Loss)
+    }
+
+    /// Outcome of playing `self` against `other`.
+    pub fn against(&self, other: Move) -> Outcome {
+        RULES.against(self.index(), other.index())
+    }
+
+    /// The move to play against `opponent` to end the round with `outcome`.
+    pub fn for_outcome(opponent: Move, outcome: Outcome) -> Move {
+        Move::ALL[RULES.for_outcome(opponent.index(), outcome)]
+    }
+
+    /// Index of the move in [`Ruleset::rock_paper_scissors`].
+    fn index(self) -> usize {
+        self as usize - 1
     }
 }
 
+/// The rules every [`Move`] is played by.
+static RULES: LazyLock<Ruleset> = LazyLock::new(Ruleset::rock_paper_scissors);
+
 impl FromStr for Move {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
-            "A" | "X" => Ok(Rock),
-            "B" | "Y" => Ok(Paper),
-            "C" | "Z" => Ok(Scissors),
+            "A" => Ok(Rock),
+            "B" => Ok(Paper),
+            "C" => Ok(Scissors),
             _ => Err("Not a move".to_string()),
         }
     }
 }
 
-pub fn part1(input: &str) -> String {
-    let result = input
-        .lines()
-        .map(|line| {
-            let moves = line
-                .split(" ")
-                .map(|mov| mov.parse::<Move>().unwrap())
-                .collect::<Vec<_>>();
-
-            match moves[0].partial_cmp(&moves[1]) {
-                None => panic!("moves should be comparable"),
-                Some(Ordering::Equal) => 3 + moves[1] as u32,
-                Some(Ordering::Greater) => moves[1] as u32,
-                Some(Ordering::Less) => 6 + moves[1] as u32,
-            }
+/// Second column of the strategy guide, whose meaning differs between the parts.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Column {
+    X,
+    Y,
+    Z,
+}
+
+impl Column {
+    pub const ALL: [Column; 3] = [X, Y, Z];
+
+    /// Reading of the first part: the move to play.
+    pub fn as_move(self) -> Move {
+        match self {
+            X => Rock,
+            Y => Paper,
+            Z => Scissors,
+        }
+    }
+
+    /// Reading of the second part: how the round must end.
+    pub fn as_outcome(self) -> Outcome {
+        match self {
+            X => Outcome::Loss,
+            Y => Outcome::Draw,
+            Z => Outcome::Win,
+        }
+    }
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "X" => Ok(X),
+            "Y" => Ok(Y),
+            "Z" => Ok(Z),
+            _ => Err("Not a column".to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum Error {
+    #[error("invalid move {token:?} at {position}")]
+    InvalidMove { position: Position, token: String },
+    #[error("invalid column {token:?} at {position}")]
+    InvalidColumn { position: Position, token: String },
+    #[error("expected two columns in {token:?} at {position}")]
+    MalformedRound { position: Position, token: String },
+}
+
+/// Example from the puzzle description.
+pub const EXAMPLE: &str = "A Y
+B X
+C Z";
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Rock Paper Scissors";
+
+    type Input<'a> = Vec<(Move, Column)>;
+    type Output1 = u32;
+    type Output2 = u32;
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        parse_guide(
+            input,
+            |token| token.parse().ok(),
+            |token| token.parse().ok(),
+        )
+    }
+
+    fn part1(guide: &Self::Input<'_>) -> Result<Self::Output1, Self::Error> {
+        Ok(play(guide, |opponent, column| {
+            Round::new(opponent, column.as_move())
         })
-        .sum::<u32>();
+        .total())
+    }
 
-    result.to_string()
+    fn part2(guide: &Self::Input<'_>) -> Result<Self::Output2, Self::Error> {
+        Ok(play(guide, |opponent, column| {
+            Round::new(opponent, Move::for_outcome(opponent, column.as_outcome()))
+        })
+        .total())
+    }
 }
 
-pub fn part2(input: &str) -> String {
-    let result = input
+/// Reads each line of a strategy guide into a pair, interpreting its two columns with
+/// `opponent` and `column`.
+pub(crate) fn parse_guide<O, C>(
+    input: &str,
+    opponent: impl Fn(&str) -> Option<O>,
+    column: impl Fn(&str) -> Option<C>,
+) -> Result<Vec<(O, C)>, Error> {
+    input
         .lines()
         .map(|line| {
-            let moves = line.split(" ").collect::<Vec<_>>();
-
-            let opponent = moves[0].parse::<Move>().unwrap();
-
-            let player = match moves[1] {
-                "X" => match opponent {
-                    Rock => Scissors,
-                    Paper => Rock,
-                    Scissors => Paper,
-                },
-                "Y" => opponent,
-                "Z" => match opponent {
-                    Rock => Paper,
-                    Paper => Scissors,
-                    Scissors => Rock,
-                },
-                _ => !panic!(),
-            };
-
-            match opponent.partial_cmp(&player) {
-                None => panic!("moves should be comparable"),
-                Some(Ordering::Equal) => 3 + player as u32,
-                Some(Ordering::Greater) => player as u32,
-                Some(Ordering::Less) => 6 + player as u32,
-            }
+            let (first, second) = line.split_once(' ').ok_or_else(|| Error::MalformedRound {
+                position: Position::of(input, line),
+                token: line.to_string(),
+            })?;
+
+            let opponent = opponent(first).ok_or_else(|| Error::InvalidMove {
+                position: Position::of(input, first),
+                token: first.to_string(),
+            })?;
+            let column = column(second).ok_or_else(|| Error::InvalidColumn {
+                position: Position::of(input, second),
+                token: second.to_string(),
+            })?;
+
+            Ok((opponent, column))
         })
-        .sum::<u32>();
+        .collect()
+}
+
+/// Plays the guide, reading each line into a round with `round`.
+pub fn play(guide: &[(Move, Column)], round: impl Fn(Move, Column) -> Round) -> Tournament {
+    guide
+        .iter()
+        .map(|&(opponent, column)| round(opponent, column))
+        .collect()
+}
 
-    result.to_string()
+pub fn part1(input: &str) -> Result<String, Error> {
+    Day02::solve(Part::One, input)
+}
+
+pub fn part2(input: &str) -> Result<String, Error> {
+    Day02::solve(Part::Two, input)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &str = "A Y
-B X
-C Z";
-
     #[test]
     fn part1_works() {
-        let result = part1(INPUT);
+        let result = part1(EXAMPLE).unwrap();
         assert_eq!(result, "15");
     }
 
     #[test]
     fn part2_works() {
-        let result = part2(INPUT);
+        let result = part2(EXAMPLE).unwrap();
         assert_eq!(result, "12");
     }
+
+    #[test]
+    fn against_and_for_outcome_agree() {
+        for opponent in Move::ALL {
+            for player in Move::ALL {
+                let outcome = player.against(opponent);
+                assert_eq!(opponent.against(player), outcome.reverse());
+                assert_eq!(Move::for_outcome(opponent, outcome), player);
+            }
+        }
+
+        assert_eq!(Paper.against(Rock), Outcome::Win);
+        assert_eq!(Rock.against(Paper), Outcome::Loss);
+        assert_eq!(Move::for_outcome(Scissors, Outcome::Win), Rock);
+    }
+
+    #[test]
+    fn invalid_column_is_located() {
+        let result = part2("A Y\nB W");
+        assert_eq!(
+            result,
+            Err(Error::InvalidColumn {
+                position: Position { line: 2, column: 3 },
+                token: "W".to_string(),
+            })
+        );
+    }
 }
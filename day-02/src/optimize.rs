@@ -0,0 +1,163 @@
+use std::cmp::Reverse;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::tournament::Round;
+use crate::Move::{Paper, Rock, Scissors};
+use crate::{Column, Move, Outcome};
+
+/// One way of reading the second column of the guide: as moves or as outcomes, indexed by
+/// column.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Cipher {
+    Moves([Move; 3]),
+    Outcomes([Outcome; 3]),
+}
+
+impl Cipher {
+    /// Reading of the first part.
+    pub const PART1: Cipher = Cipher::Moves([Rock, Paper, Scissors]);
+    /// Reading of the second part.
+    pub const PART2: Cipher = Cipher::Outcomes([Outcome::Loss, Outcome::Draw, Outcome::Win]);
+
+    /// Every mapping of X, Y and Z onto distinct moves, then onto distinct outcomes.
+    pub fn all() -> impl Iterator<Item = Cipher> {
+        let moves = permutations(Move::ALL).map(Cipher::Moves);
+        let outcomes = permutations(Outcome::ALL).map(Cipher::Outcomes);
+
+        moves.into_iter().chain(outcomes)
+    }
+
+    pub fn round(&self, opponent: Move, column: Column) -> Round {
+        match self {
+            Cipher::Moves(moves) => Round::new(opponent, moves[column as usize]),
+            Cipher::Outcomes(outcomes) => Round::new(
+                opponent,
+                Move::for_outcome(opponent, outcomes[column as usize]),
+            ),
+        }
+    }
+}
+
+impl Display for Cipher {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let names: [String; 3] = match self {
+            Cipher::Moves(moves) => moves.map(|m| format!("{m:?}")),
+            Cipher::Outcomes(outcomes) => outcomes.map(|o| format!("{o:?}")),
+        };
+
+        write!(f, "X={} Y={} Z={}", names[0], names[1], names[2])
+    }
+}
+
+fn permutations<T: Copy>([a, b, c]: [T; 3]) -> [[T; 3]; 6] {
+    [
+        [a, b, c],
+        [a, c, b],
+        [b, a, c],
+        [b, c, a],
+        [c, a, b],
+        [c, b, a],
+    ]
+}
+
+/// Total score the guide yields under a cipher.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Rated {
+    pub cipher: Cipher,
+    pub score: u32,
+}
+
+/// Highest- and lowest-scoring readings of a guide.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Ranking {
+    pub best: Rated,
+    pub worst: Rated,
+}
+
+/// Scores the guide under every cipher, best first; ties keep the order of [`Cipher::all`].
+pub fn rate_all(guide: &[(Move, Column)]) -> Vec<Rated> {
+    // Only 9 distinct lines exist, so each cipher is scored from their counts.
+    let mut counts = [[0_u32; 3]; 3];
+    for &(opponent, column) in guide {
+        counts[opponent as usize - 1][column as usize] += 1;
+    }
+
+    let mut rated: Vec<_> = Cipher::all()
+        .map(|cipher| {
+            let score = Move::ALL
+                .iter()
+                .flat_map(|&opponent| Column::ALL.map(|column| (opponent, column)))
+                .map(|(opponent, column)| {
+                    counts[opponent as usize - 1][column as usize]
+                        * cipher.round(opponent, column).score()
+                })
+                .sum();
+
+            Rated { cipher, score }
+        })
+        .collect();
+
+    rated.sort_by_key(|rated| Reverse(rated.score));
+    rated
+}
+
+/// The cipher most and least favourable to the player, i.e. the likeliest and unlikeliest
+/// meanings of the guide.
+///
+/// Ties on either end go to the cipher that comes first in [`Cipher::all`].
+pub fn optimize(guide: &[(Move, Column)]) -> Ranking {
+    let rated = rate_all(guide);
+    let lowest = rated[rated.len() - 1].score;
+
+    Ranking {
+        best: rated[0],
+        worst: *rated.iter().find(|rated| rated.score == lowest).unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{play, Day02, EXAMPLE};
+    use aoc_core::Solution;
+
+    #[test]
+    fn every_cipher_is_rated() {
+        let guide = Day02::parse(EXAMPLE).unwrap();
+        let rated = rate_all(&guide);
+
+        assert_eq!(rated.len(), 12);
+        for Rated { cipher, score } in &rated {
+            let tournament = play(&guide, |opponent, column| cipher.round(opponent, column));
+            assert_eq!(tournament.total(), *score, "{cipher}");
+        }
+
+        let score = |cipher| rated.iter().find(|r| r.cipher == cipher).unwrap().score;
+        assert_eq!(score(Cipher::PART1), 15);
+        assert_eq!(score(Cipher::PART2), 12);
+    }
+
+    #[test]
+    fn example_ranking() {
+        let guide = Day02::parse(EXAMPLE).unwrap();
+        let ranking = optimize(&guide);
+
+        assert_eq!(ranking.best.score, 24);
+        assert_eq!(ranking.best.cipher.to_string(), "X=Scissors Y=Paper Z=Rock");
+        assert_eq!(ranking.worst.score, 6);
+        assert_eq!(
+            ranking.worst.cipher.to_string(),
+            "X=Rock Y=Scissors Z=Paper"
+        );
+    }
+
+    #[test]
+    fn ties_go_to_the_first_cipher() {
+        let first = Cipher::all().next().unwrap();
+        let ranking = optimize(&[]);
+
+        assert_eq!(ranking.best.cipher, first);
+        assert_eq!(ranking.worst.cipher, first);
+    }
+}
@@ -0,0 +1,58 @@
+use proptest::prelude::*;
+
+use crate::ruleset::{Reading, Ruleset};
+use crate::{generate, part1, part2};
+
+/// Score of every possible round, read straight off the rules.
+fn score(input: &str, table: [(&str, u32); 9]) -> u32 {
+    input
+        .lines()
+        .map(|line| table.iter().find(|(round, _)| *round == line).unwrap().1)
+        .sum()
+}
+
+const MOVES: [(&str, u32); 9] = [
+    ("A X", 1 + 3),
+    ("A Y", 2 + 6),
+    ("A Z", 3),
+    ("B X", 1),
+    ("B Y", 2 + 3),
+    ("B Z", 3 + 6),
+    ("C X", 1 + 6),
+    ("C Y", 2),
+    ("C Z", 3 + 3),
+];
+
+const OUTCOMES: [(&str, u32); 9] = [
+    ("A X", 3),
+    ("A Y", 1 + 3),
+    ("A Z", 2 + 6),
+    ("B X", 1),
+    ("B Y", 2 + 3),
+    ("B Z", 3 + 6),
+    ("C X", 2),
+    ("C Y", 3 + 3),
+    ("C Z", 1 + 6),
+];
+
+proptest! {
+    #[test]
+    fn part1_matches_reference(seed in any::<u64>(), rounds in 1..500usize) {
+        let input = generate::input(seed, rounds);
+        prop_assert_eq!(part1(&input).unwrap(), score(&input, MOVES).to_string());
+    }
+
+    #[test]
+    fn part2_matches_reference(seed in any::<u64>(), rounds in 1..500usize) {
+        let input = generate::input(seed, rounds);
+        prop_assert_eq!(part2(&input).unwrap(), score(&input, OUTCOMES).to_string());
+    }
+
+    #[test]
+    fn rock_paper_scissors_ruleset_matches_parts(seed in any::<u64>(), rounds in 1..500usize) {
+        let input = generate::input(seed, rounds);
+        let rps = Ruleset::rock_paper_scissors();
+        prop_assert_eq!(rps.play(&input, Reading::Moves).unwrap().to_string(), part1(&input).unwrap());
+        prop_assert_eq!(rps.play(&input, Reading::Outcomes).unwrap().to_string(), part2(&input).unwrap());
+    }
+}
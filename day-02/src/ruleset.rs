@@ -0,0 +1,502 @@
+//! Cyclic games generalizing Rock Paper Scissors to any odd number of moves.
+//!
+//! In a balanced cyclic game of at least three moves every move beats exactly half of the
+//! others. The puzzle's game is the three-move case, [`Ruleset::rock_paper_scissors`].
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::{parse_guide, Column, Error, Outcome};
+
+/// A ruleset that cannot be played.
+#[derive(Debug, thiserror::Error)]
+pub enum RulesetError {
+    #[error("a cyclic game needs at least 3 moves, got {count}")]
+    TooFewMoves { count: usize },
+    #[error("a cyclic game needs an odd number of moves, got {count}")]
+    EvenMoves { count: usize },
+    #[error("a cyclic game has at most 64 moves, got {count}")]
+    TooManyMoves { count: usize },
+    #[error("move {name:?} is listed twice")]
+    DuplicateMove { name: String },
+    #[error("unknown move {name:?} in the dominance table")]
+    UnknownMove { name: String },
+    #[error("{name:?} cannot beat itself")]
+    BeatsItself { name: String },
+    #[error("{first:?} and {second:?} beat each other")]
+    MutualWin { first: String, second: String },
+    #[error("neither {first:?} nor {second:?} beats the other")]
+    Undecided { first: String, second: String },
+    #[error("{name:?} beats {count} moves instead of {expected}")]
+    Unbalanced {
+        name: String,
+        count: usize,
+        expected: usize,
+    },
+    #[error("invalid ruleset: {0}")]
+    Config(#[from] toml::de::Error),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// Ruleset as written in a TOML config file.
+///
+/// ```toml
+/// name = "Rock Paper Scissors"
+/// moves = ["Rock", "Paper", "Scissors"]
+///
+/// [beats]
+/// Rock = ["Scissors"]
+/// Paper = ["Rock"]
+/// Scissors = ["Paper"]
+/// ```
+///
+/// Without a `[beats]` table, each move beats the half of the moves listed before it,
+/// wrapping around.
+#[derive(Debug, Deserialize)]
+struct Config {
+    name: Option<String>,
+    moves: Vec<String>,
+    beats: Option<BTreeMap<String, Vec<String>>>,
+}
+
+/// How to read the second column of a guide.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Reading {
+    /// The move to play, named by the last letters of the alphabet (`X`, `Y`, `Z` for three
+    /// moves).
+    Moves,
+    /// How the round must end: `X` to lose, `Y` to draw, `Z` to win.
+    Outcomes,
+}
+
+/// Moves of a balanced cyclic game with the dominance table between them.
+///
+/// Moves are referred to by index; a move scores its index plus one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ruleset {
+    name: String,
+    moves: Vec<String>,
+    /// Bit `j` of `beats[i]` is set when move `i` beats move `j`.
+    beats: Vec<u64>,
+}
+
+impl Ruleset {
+    /// Game in which each move beats the `(n - 1) / 2` moves listed before it, wrapping around.
+    pub fn cyclic(name: &str, moves: &[&str]) -> Result<Self, RulesetError> {
+        let count = moves.len();
+        check_count(count)?;
+
+        let beats = (0..count)
+            .map(|i| {
+                (1..=count / 2)
+                    .map(|offset| (i + count - offset) % count)
+                    .fold(0, |mask, j| mask | 1 << j)
+            })
+            .collect();
+
+        Self::validated(name, moves.iter().map(|m| m.to_string()).collect(), beats)
+    }
+
+    /// Game with an explicit dominance table, mapping each move to the moves it beats.
+    pub fn with_table(
+        name: &str,
+        moves: Vec<String>,
+        table: &BTreeMap<String, Vec<String>>,
+    ) -> Result<Self, RulesetError> {
+        check_count(moves.len())?;
+
+        let index = |name: &str| {
+            moves
+                .iter()
+                .position(|m| m == name)
+                .ok_or_else(|| RulesetError::UnknownMove {
+                    name: name.to_string(),
+                })
+        };
+
+        let mut beats = vec![0_u64; moves.len()];
+        for (winner, losers) in table {
+            let winner = index(winner)?;
+            for loser in losers {
+                beats[winner] |= 1 << index(loser)?;
+            }
+        }
+
+        Self::validated(name, moves, beats)
+    }
+
+    pub fn rock_paper_scissors() -> Self {
+        Self::cyclic("Rock Paper Scissors", &["Rock", "Paper", "Scissors"])
+            .expect("built-in ruleset is valid")
+    }
+
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        Self::cyclic(
+            "Rock Paper Scissors Lizard Spock",
+            &["Rock", "Spock", "Paper", "Lizard", "Scissors"],
+        )
+        .expect("built-in ruleset is valid")
+    }
+
+    /// Built-in ruleset by short name, `rps` or `rpsls`.
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "rps" => Some(Self::rock_paper_scissors()),
+            "rpsls" => Some(Self::rock_paper_scissors_lizard_spock()),
+            _ => None,
+        }
+    }
+
+    /// Reads a ruleset from TOML, as described on [`Config`].
+    pub fn from_toml(config: &str) -> Result<Self, RulesetError> {
+        let config: Config = toml::from_str(config)?;
+        let name = config.name.unwrap_or_else(|| config.moves.join(" "));
+
+        match config.beats {
+            Some(table) => Self::with_table(&name, config.moves, &table),
+            None => {
+                let moves: Vec<_> = config.moves.iter().map(String::as_str).collect();
+                Self::cyclic(&name, &moves)
+            }
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, RulesetError> {
+        Self::from_toml(&fs::read_to_string(path)?)
+    }
+
+    fn validated(name: &str, moves: Vec<String>, beats: Vec<u64>) -> Result<Self, RulesetError> {
+        let ruleset = Ruleset {
+            name: name.to_string(),
+            moves,
+            beats,
+        };
+        let count = ruleset.len();
+
+        for (i, first) in ruleset.moves.iter().enumerate() {
+            if let Some(second) = ruleset.moves[..i].iter().find(|m| *m == first) {
+                return Err(RulesetError::DuplicateMove {
+                    name: second.clone(),
+                });
+            }
+
+            if ruleset.beats(i, i) {
+                return Err(RulesetError::BeatsItself {
+                    name: first.clone(),
+                });
+            }
+
+            for (j, second) in ruleset.moves.iter().enumerate().skip(i + 1) {
+                let pair = (first.clone(), second.clone());
+                match (ruleset.beats(i, j), ruleset.beats(j, i)) {
+                    (true, true) => {
+                        return Err(RulesetError::MutualWin {
+                            first: pair.0,
+                            second: pair.1,
+                        })
+                    }
+                    (false, false) => {
+                        return Err(RulesetError::Undecided {
+                            first: pair.0,
+                            second: pair.1,
+                        })
+                    }
+                    _ => (),
+                }
+            }
+
+            let wins = ruleset.beats[i].count_ones() as usize;
+            if wins != count / 2 {
+                return Err(RulesetError::Unbalanced {
+                    name: first.clone(),
+                    count: wins,
+                    expected: count / 2,
+                });
+            }
+        }
+
+        Ok(ruleset)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn moves(&self) -> &[String] {
+        &self.moves
+    }
+
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.moves.iter().position(|m| m == name)
+    }
+
+    pub fn beats(&self, winner: usize, loser: usize) -> bool {
+        self.beats[winner] & 1 << loser != 0
+    }
+
+    /// Outcome of playing `player` against `opponent`.
+    pub fn against(&self, player: usize, opponent: usize) -> Outcome {
+        if player == opponent {
+            Outcome::Draw
+        } else if self.beats(player, opponent) {
+            Outcome::Win
+        } else {
+            Outcome::Loss
+        }
+    }
+
+    /// The lowest-scoring move ending the round against `opponent` with `outcome`.
+    pub fn for_outcome(&self, opponent: usize, outcome: Outcome) -> usize {
+        (0..self.len())
+            .find(|&player| self.against(player, opponent) == outcome)
+            .expect("a balanced game allows every outcome")
+    }
+
+    /// Score of the move played plus the score of the outcome.
+    pub fn score(&self, player: usize, opponent: usize) -> u32 {
+        player as u32 + 1 + self.against(player, opponent).score()
+    }
+
+    /// Total score of a guide whose first column names the opponent's move with the first
+    /// letters of the alphabet (`A`, `B`, `C` for three moves).
+    pub fn play(&self, input: &str, reading: Reading) -> Result<u32, Error> {
+        let first = |token: &str| letter(token).filter(|&l| l < self.len());
+        let last = |token: &str| {
+            letter(token)
+                .and_then(|l| (l + self.len()).checked_sub(26))
+                .filter(|&l| l < self.len())
+        };
+
+        let rounds = match reading {
+            Reading::Moves => parse_guide(input, first, last)?,
+            Reading::Outcomes => parse_guide(input, first, |token| token.parse::<Column>().ok())?
+                .into_iter()
+                .map(|(opponent, column)| {
+                    (opponent, self.for_outcome(opponent, column.as_outcome()))
+                })
+                .collect(),
+        };
+
+        Ok(rounds
+            .into_iter()
+            .map(|(opponent, player)| self.score(player, opponent))
+            .sum())
+    }
+}
+
+fn check_count(count: usize) -> Result<(), RulesetError> {
+    if count < 3 {
+        Err(RulesetError::TooFewMoves { count })
+    } else if count.is_multiple_of(2) {
+        Err(RulesetError::EvenMoves { count })
+    } else if count > 64 {
+        Err(RulesetError::TooManyMoves { count })
+    } else {
+        Ok(())
+    }
+}
+
+/// Index of a single capital letter in the alphabet.
+fn letter(token: &str) -> Option<usize> {
+    match token.as_bytes() {
+        [letter @ b'A'..=b'Z'] => Some((letter - b'A') as usize),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_core::Position;
+
+    use super::*;
+    use crate::{Move, EXAMPLE};
+
+    #[test]
+    fn rock_paper_scissors_matches_moves() {
+        let rps = Ruleset::rock_paper_scissors();
+
+        for opponent in Move::ALL {
+            for player in Move::ALL {
+                let (o, p) = (opponent as usize - 1, player as usize - 1);
+                assert_eq!(rps.against(p, o), player.against(opponent));
+            }
+            for outcome in Outcome::ALL {
+                let o = opponent as usize - 1;
+                let expected = Move::for_outcome(opponent, outcome) as usize - 1;
+                assert_eq!(rps.for_outcome(o, outcome), expected);
+            }
+        }
+
+        assert_eq!(rps.play(EXAMPLE, Reading::Moves), Ok(15));
+        assert_eq!(rps.play(EXAMPLE, Reading::Outcomes), Ok(12));
+    }
+
+    #[test]
+    fn lizard_spock_rules() {
+        let rpsls = Ruleset::builtin("rpsls").unwrap();
+        let beats =
+            |winner, loser| rpsls.beats(rpsls.index(winner).unwrap(), rpsls.index(loser).unwrap());
+
+        for (winner, loser) in [
+            ("Scissors", "Paper"),
+            ("Paper", "Rock"),
+            ("Rock", "Lizard"),
+            ("Lizard", "Spock"),
+            ("Spock", "Scissors"),
+            ("Scissors", "Lizard"),
+            ("Lizard", "Paper"),
+            ("Paper", "Spock"),
+            ("Spock", "Rock"),
+            ("Rock", "Scissors"),
+        ] {
+            assert!(beats(winner, loser), "{winner} beats {loser}");
+            assert!(!beats(loser, winner), "{loser} loses to {winner}");
+        }
+
+        // Against Lizard (D): Spock (W) loses, Rock (V) and Scissors (Z) win.
+        assert_eq!(
+            rpsls.play("D W\nD V\nD Z", Reading::Moves),
+            Ok(2 + (1 + 6) + (5 + 6))
+        );
+        // Losing picks Spock, drawing Lizard and winning Rock, the cheapest of each.
+        assert_eq!(
+            rpsls.play("D X\nD Y\nD Z", Reading::Outcomes),
+            Ok(2 + (4 + 3) + (1 + 6))
+        );
+    }
+
+    #[test]
+    fn rulesets_load_from_toml() {
+        let explicit = Ruleset::from_toml(
+            r#"
+            name = "Rock Paper Scissors"
+            moves = ["Rock", "Paper", "Scissors"]
+
+            [beats]
+            Rock = ["Scissors"]
+            Paper = ["Rock"]
+            Scissors = ["Paper"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(explicit, Ruleset::rock_paper_scissors());
+
+        let cyclic =
+            Ruleset::from_toml(r#"moves = ["Rock", "Spock", "Paper", "Lizard", "Scissors"]"#)
+                .unwrap();
+        assert_eq!(cyclic.name(), "Rock Spock Paper Lizard Scissors");
+        assert_eq!(
+            cyclic.moves(),
+            Ruleset::rock_paper_scissors_lizard_spock().moves()
+        );
+    }
+
+    #[test]
+    fn invalid_rulesets_are_rejected() {
+        let error = |config: &str| Ruleset::from_toml(config).unwrap_err().to_string();
+
+        assert_eq!(
+            error(r#"moves = ["Rock", "Paper", "Scissors", "Spock"]"#),
+            "a cyclic game needs an odd number of moves, got 4"
+        );
+        assert_eq!(
+            error(r#"moves = ["Rock", "Paper"]"#),
+            "a cyclic game needs at least 3 moves, got 2"
+        );
+        assert_eq!(
+            error(r#"moves = ["Rock"]"#),
+            "a cyclic game needs at least 3 moves, got 1"
+        );
+        assert_eq!(
+            error("moves = []"),
+            "a cyclic game needs at least 3 moves, got 0"
+        );
+        assert_eq!(
+            error(r#"moves = ["Rock", "Paper", "Rock"]"#),
+            "move \"Rock\" is listed twice"
+        );
+        assert_eq!(
+            error(
+                r#"
+                moves = ["Rock", "Paper", "Scissors"]
+                beats = { Rock = ["Scissors", "Paper"], Paper = ["Rock"], Scissors = ["Paper"] }
+                "#
+            ),
+            "\"Rock\" and \"Paper\" beat each other"
+        );
+        assert_eq!(
+            error(
+                r#"
+                moves = ["Rock", "Paper", "Scissors"]
+                beats = { Rock = ["Scissors"], Paper = ["Rock"] }
+                "#
+            ),
+            "neither \"Paper\" nor \"Scissors\" beats the other"
+        );
+        assert_eq!(
+            error(
+                r#"
+                moves = ["Rock", "Paper", "Scissors"]
+                beats = { Rock = ["Well"] }
+                "#
+            ),
+            "unknown move \"Well\" in the dominance table"
+        );
+    }
+
+    #[test]
+    fn unbalanced_tables_are_rejected() {
+        // A transitive order decides every pair but is not cyclic.
+        let table = BTreeMap::from([
+            ("A".to_string(), vec!["B".to_string(), "C".to_string()]),
+            ("B".to_string(), vec!["C".to_string()]),
+        ]);
+        let moves = vec!["A".to_string(), "B".to_string(), "C".to_string()];
+
+        assert_eq!(
+            Ruleset::with_table("order", moves, &table)
+                .unwrap_err()
+                .to_string(),
+            "\"A\" beats 2 moves instead of 1"
+        );
+    }
+
+    #[test]
+    fn guide_letters_are_checked() {
+        let rps = Ruleset::rock_paper_scissors();
+        assert_eq!(
+            rps.play("A Y\nD X", Reading::Moves),
+            Err(Error::InvalidMove {
+                position: Position { line: 2, column: 1 },
+                token: "D".to_string(),
+            })
+        );
+        assert_eq!(
+            rps.play("A W", Reading::Moves),
+            Err(Error::InvalidColumn {
+                position: Position { line: 1, column: 3 },
+                token: "W".to_string(),
+            })
+        );
+        assert_eq!(
+            rps.play("A Y\nBX", Reading::Outcomes),
+            Err(Error::MalformedRound {
+                position: Position { line: 2, column: 1 },
+                token: "BX".to_string(),
+            })
+        );
+    }
+}
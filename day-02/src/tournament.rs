@@ -0,0 +1,117 @@
+use crate::{Move, Outcome};
+
+/// One round, from the point of view of the player following the guide.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Round {
+    pub opponent: Move,
+    pub player: Move,
+}
+
+impl Round {
+    pub fn new(opponent: Move, player: Move) -> Self {
+        Round { opponent, player }
+    }
+
+    pub fn outcome(&self) -> Outcome {
+        self.player.against(self.opponent)
+    }
+
+    /// Score of the shape played plus the score of the outcome.
+    pub fn score(&self) -> u32 {
+        self.player.score() + self.outcome().score()
+    }
+}
+
+/// Number of rounds won, drawn and lost.
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub struct Record {
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+/// All rounds of a strategy guide, in order.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Tournament {
+    rounds: Vec<Round>,
+}
+
+impl Tournament {
+    pub fn new(rounds: Vec<Round>) -> Self {
+        Tournament { rounds }
+    }
+
+    pub fn rounds(&self) -> &[Round] {
+        &self.rounds
+    }
+
+    pub fn scores(&self) -> impl Iterator<Item = u32> + '_ {
+        self.rounds.iter().map(Round::score)
+    }
+
+    pub fn total(&self) -> u32 {
+        self.scores().sum()
+    }
+
+    /// Running total after each round.
+    pub fn cumulative(&self) -> impl Iterator<Item = u32> + '_ {
+        self.scores().scan(0, |total, score| {
+            *total += score;
+            Some(*total)
+        })
+    }
+
+    pub fn record(&self) -> Record {
+        self.rounds
+            .iter()
+            .fold(Record::default(), |mut record, round| {
+                match round.outcome() {
+                    Outcome::Win => record.wins += 1,
+                    Outcome::Draw => record.draws += 1,
+                    Outcome::Loss => record.losses += 1,
+                }
+                record
+            })
+    }
+}
+
+impl FromIterator<Round> for Tournament {
+    fn from_iter<T: IntoIterator<Item = Round>>(iter: T) -> Self {
+        Tournament::new(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{play, Day02, EXAMPLE};
+    use aoc_core::Solution;
+
+    #[test]
+    fn example_tournament() {
+        let guide = Day02::parse(EXAMPLE).unwrap();
+        let tournament = play(&guide, |opponent, column| {
+            Round::new(opponent, column.as_move())
+        });
+
+        assert_eq!(tournament.scores().collect::<Vec<_>>(), [8, 1, 6]);
+        assert_eq!(tournament.cumulative().collect::<Vec<_>>(), [8, 9, 15]);
+        assert_eq!(
+            tournament.record(),
+            Record {
+                wins: 1,
+                draws: 1,
+                losses: 1
+            }
+        );
+        assert_eq!(tournament.total(), 15);
+    }
+
+    #[test]
+    fn empty_tournament() {
+        let tournament = Tournament::default();
+        assert_eq!(tournament.total(), 0);
+        assert_eq!(tournament.cumulative().count(), 0);
+        assert_eq!(tournament.record(), Record::default());
+    }
+}
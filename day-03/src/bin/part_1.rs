@@ -1,7 +1,10 @@
+use std::env;
+use std::process::ExitCode;
+
+use aoc_core::input;
 use day_03::part1;
-use std::fs;
 
-fn main() {
-    let file = fs::read_to_string("./input.txt").unwrap();
-    println!("{}", part1(&file));
+fn main() -> ExitCode {
+    let paths = input::paths(env::args().skip(1), "./input.txt");
+    input::solve_all(&paths, part1)
 }
@@ -0,0 +1,66 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+/// Rucksack list with `groups` groups of three elves.
+///
+/// Each group shares exactly one badge, and each rucksack has exactly one item type
+/// in both compartments.
+pub fn input(seed: u64, groups: usize) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let letters = ('a'..='z').chain('A'..='Z').collect::<Vec<_>>();
+
+    (0..groups)
+        .flat_map(|_| {
+            let mut letters = letters.clone();
+            letters.shuffle(&mut rng);
+            let (badge, rest) = letters.split_first().unwrap();
+
+            // The remaining 51 letters split evenly, so only the badge is common to the group.
+            rest.chunks(rest.len() / 3)
+                .map(|pool| rucksack(&mut rng, *badge, pool))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn rucksack(rng: &mut StdRng, badge: char, pool: &[char]) -> String {
+    let (shared, rest) = pool.split_first().unwrap();
+    let (left_pool, right_pool) = rest.split_at(rest.len() / 2);
+    let len = rng.gen_range(2..=16);
+
+    let mut left = (0..len)
+        .map(|_| *left_pool.choose(rng).unwrap())
+        .collect::<Vec<_>>();
+    let mut right = (0..len)
+        .map(|_| *right_pool.choose(rng).unwrap())
+        .collect::<Vec<_>>();
+
+    left[0] = *shared;
+    right[0] = *shared;
+    if rng.gen_bool(0.5) {
+        left[1] = badge;
+    } else {
+        right[1] = badge;
+    }
+
+    left.shuffle(rng);
+    right.shuffle(rng);
+
+    left.into_iter().chain(right).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn generated_input_is_valid() {
+        let generated = input(3, 200);
+        assert_eq!(generated, input(3, 200));
+        assert!(part1(&generated).is_ok());
+        assert!(part2(&generated).is_ok());
+    }
+}
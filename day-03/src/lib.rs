@@ -1,71 +1,258 @@
-pub fn part1(input: &str) -> String {
-    let letters = ('a'..='z').chain('A'..='Z').collect::<Vec<_>>();
-    let result = input
-        .lines()
-        .map(|line| {
-            let half = line.len() / 2;
-
-            let left = &line[0..half];
-            let right = &line[half..(half * 2)];
-
-            let common = left.chars().find(|c| right.contains(*c)).unwrap();
-            let code = letters
-                .iter()
-                .enumerate()
-                .find_map(|(idx, item)| if *item == common { Some(idx + 1) } else { None })
-                .unwrap();
-
-            code
-        })
-        .sum::<usize>();
-    result.to_string()
+pub mod generate;
+#[cfg(test)]
+mod reference;
+pub mod rucksack;
+
+use aoc_core::{Part, Position, Solution};
+use thiserror::Error;
+
+use crate::rucksack::{group_badge, group_badges, priority, Rucksack};
+
+#[derive(Debug, Clone, Error, PartialEq)]
+pub enum Error {
+    #[error("no item shared by both compartments of {token:?} on line {line}")]
+    NoCommonItem { line: usize, token: String },
+    #[error("items {items:?} are all in both compartments of {token:?} on line {line}")]
+    MultipleCommonItems {
+        line: usize,
+        token: String,
+        items: String,
+    },
+    #[error("rucksack on line {line} has an odd number of items ({len})")]
+    OddRucksack { line: usize, len: usize },
+    #[error("no badge shared by the group starting on line {line}")]
+    NoBadge { line: usize },
+    #[error("group starting on line {line} shares several badges {badges:?}")]
+    MultipleBadges { line: usize, badges: String },
+    #[error("group starting on line {line} has {size} rucksacks instead of {expected}")]
+    IncompleteGroup {
+        line: usize,
+        size: usize,
+        expected: usize,
+    },
+    #[error("invalid item {item:?} at {position}")]
+    InvalidItem { position: Position, item: char },
 }
 
-pub fn part2(input: &str) -> String {
-    let letters = ('a'..='z').chain('A'..='Z').collect::<Vec<_>>();
-
-    let lines = input.lines().collect::<Vec<_>>();
-    let lines_step = lines.iter().step_by(3).enumerate();
-    let result = lines_step
-        .map(|(idx, &line)| {
-            let common = line
-                .chars()
-                .find(|c| lines[(idx * 3) + 1].contains(*c) && lines[(idx * 3) + 2].contains(*c))
-                .unwrap();
-
-            let code = letters
-                .iter()
-                .enumerate()
-                .find_map(|(idx, item)| if *item == common { Some(idx + 1) } else { None })
-                .unwrap();
-
-            code
-        })
-        .sum::<usize>();
-
-    result.to_string()
+fn rucksack<'a>(input: &str, line: &'a str) -> Result<Rucksack<'a>, Error> {
+    Rucksack::new(line).map_err(|idx| Error::InvalidItem {
+        position: Position::of(input, &line[idx..]),
+        item: line[idx..].chars().next().unwrap_or_default(),
+    })
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Every problem in `input` for groups of `group_size`, in line order, rather than just the
+/// first one the solver stops at.
+///
+/// # Panics
+///
+/// When `group_size` is zero.
+pub fn validate(input: &str, group_size: usize) -> Vec<Error> {
+    assert!(group_size > 0, "groups need at least one rucksack");
+
+    let rucksacks: Vec<_> = input.lines().map(|line| rucksack(input, line)).collect();
+    let mut errors = Vec::new();
+
+    for (idx, rucksack) in rucksacks.iter().enumerate() {
+        match rucksack {
+            Ok(rucksack) => errors.extend(rucksack.misplaced_item(idx + 1).err()),
+            Err(e) => errors.push(e.clone()),
+        }
+    }
+
+    for (group, members) in rucksacks.chunks(group_size).enumerate() {
+        let line = group * group_size + 1;
+        if members.len() != group_size {
+            errors.push(Error::IncompleteGroup {
+                line,
+                size: members.len(),
+                expected: group_size,
+            });
+            continue;
+        }
+
+        // Invalid members were reported above, and leave no badge to look for.
+        let members: Result<Vec<_>, _> = members.iter().cloned().collect();
+        if let Ok(members) = members {
+            errors.extend(group_badge(&members, line, group_size).err());
+        }
+    }
+
+    errors.sort_by_key(Error::line);
+    errors
+}
+
+impl Error {
+    /// 1-based line the problem was found on, or the first line of the group.
+    pub fn line(&self) -> usize {
+        match self {
+            Error::NoCommonItem { line, .. }
+            | Error::MultipleCommonItems { line, .. }
+            | Error::OddRucksack { line, .. }
+            | Error::NoBadge { line }
+            | Error::MultipleBadges { line, .. }
+            | Error::IncompleteGroup { line, .. } => *line,
+            Error::InvalidItem { position, .. } => position.line,
+        }
+    }
+}
 
-    const INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
+/// Example from the puzzle description.
+pub const EXAMPLE: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
 jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
 PmmdzqPrVvPwwTWBwg
 wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
 ttgJtRGJQctTZtZT
 CrZsJsPPZsGzwwsLwLmpwMDw";
 
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Rucksack Reorganization";
+
+    type Input<'a> = Vec<Rucksack<'a>>;
+    type Output1 = u32;
+    type Output2 = u32;
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        input.lines().map(|line| rucksack(input, line)).collect()
+    }
+
+    fn part1(rucksacks: &Self::Input<'_>) -> Result<Self::Output1, Self::Error> {
+        rucksacks
+            .iter()
+            .enumerate()
+            .map(|(idx, rucksack)| {
+                let item = rucksack.misplaced_item(idx + 1)?;
+                Ok(priority(item).unwrap_or_default())
+            })
+            .sum()
+    }
+
+    fn part2(rucksacks: &Self::Input<'_>) -> Result<Self::Output2, Self::Error> {
+        Ok(group_badges(rucksacks, 3)?
+            .into_iter()
+            .filter_map(priority)
+            .sum())
+    }
+}
+
+pub fn part1(input: &str) -> Result<String, Error> {
+    Day03::solve(Part::One, input)
+}
+
+pub fn part2(input: &str) -> Result<String, Error> {
+    Day03::solve(Part::Two, input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn part1_works() {
-        let result = part1(INPUT);
+        let result = part1(EXAMPLE).unwrap();
         assert_eq!(result, "157");
     }
 
     #[test]
     fn part2_works() {
-        let result = part2(INPUT);
+        let result = part2(EXAMPLE).unwrap();
         assert_eq!(result, "70");
     }
+
+    #[test]
+    fn incomplete_group_is_reported() {
+        let result = part2(&EXAMPLE[..EXAMPLE.rfind('\n').unwrap()]);
+        assert_eq!(
+            result,
+            Err(Error::IncompleteGroup {
+                line: 4,
+                size: 2,
+                expected: 3
+            })
+        );
+    }
+
+    #[test]
+    fn odd_rucksack_is_reported() {
+        assert_eq!(
+            part1("vJrwpWtwJgWrhcsFMMfFFhFp\nabcab"),
+            Err(Error::OddRucksack { line: 2, len: 5 })
+        );
+    }
+
+    #[test]
+    fn validate_reports_every_problem() {
+        let input = "abab\nab1ab\nxyzXYZx\nabcA\nCcdD";
+        let errors: Vec<_> = validate(input, 3).iter().map(Error::to_string).collect();
+
+        assert_eq!(
+            errors,
+            [
+                "items \"ab\" are all in both compartments of \"abab\" on line 1",
+                "invalid item '1' at line 2, column 3",
+                "rucksack on line 3 has an odd number of items (7)",
+                "no item shared by both compartments of \"abcA\" on line 4",
+                "group starting on line 4 has 2 rucksacks instead of 3",
+                "no item shared by both compartments of \"CcdD\" on line 5",
+            ]
+        );
+
+        assert_eq!(
+            validate("abab\nab1b", 3),
+            [
+                Error::MultipleCommonItems {
+                    line: 1,
+                    token: "abab".to_string(),
+                    items: "ab".to_string(),
+                },
+                Error::IncompleteGroup {
+                    line: 1,
+                    size: 2,
+                    expected: 3,
+                },
+                Error::InvalidItem {
+                    position: Position { line: 2, column: 3 },
+                    item: '1',
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_checks_badges() {
+        let input = "aqca\nbqcb\ndqcd\nxyzx\nABCA\nDEFD";
+        assert_eq!(
+            validate(input, 3),
+            [
+                Error::MultipleBadges {
+                    line: 1,
+                    badges: "cq".to_string()
+                },
+                Error::NoBadge { line: 4 },
+            ]
+        );
+        assert!(validate(EXAMPLE, 3).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "groups need at least one rucksack")]
+    fn validate_rejects_empty_groups() {
+        validate(EXAMPLE, 0);
+    }
+
+    #[test]
+    fn invalid_item_is_located() {
+        let result = part1("abcb\nvJrw-WtwJgWrhcsFMMfFFhFp");
+        assert_eq!(
+            result,
+            Err(Error::InvalidItem {
+                position: Position { line: 2, column: 5 },
+                item: '-',
+            })
+        );
+    }
 }
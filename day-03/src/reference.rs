@@ -0,0 +1,53 @@
+use proptest::prelude::*;
+
+use crate::{generate, part1, part2};
+
+fn priority(item: char) -> u32 {
+    if item.is_ascii_lowercase() {
+        item as u32 - 'a' as u32 + 1
+    } else {
+        item as u32 - 'A' as u32 + 27
+    }
+}
+
+/// Sum of priorities of every item type found in all of `parts`.
+fn common(parts: &[&str]) -> u32 {
+    ('a'..='z')
+        .chain('A'..='Z')
+        .filter(|item| parts.iter().all(|part| part.contains(*item)))
+        .map(priority)
+        .sum()
+}
+
+fn compartments(input: &str) -> u32 {
+    input
+        .lines()
+        .map(|line| {
+            let (left, right) = line.split_at(line.len() / 2);
+            common(&[left, right])
+        })
+        .sum()
+}
+
+fn badges(input: &str) -> u32 {
+    input
+        .lines()
+        .collect::<Vec<_>>()
+        .chunks(3)
+        .map(common)
+        .sum()
+}
+
+proptest! {
+    #[test]
+    fn part1_matches_reference(seed in any::<u64>(), groups in 1..100usize) {
+        let input = generate::input(seed, groups);
+        prop_assert_eq!(part1(&input).unwrap(), compartments(&input).to_string());
+    }
+
+    #[test]
+    fn part2_matches_reference(seed in any::<u64>(), groups in 1..100usize) {
+        let input = generate::input(seed, groups);
+        prop_assert_eq!(part2(&input).unwrap(), badges(&input).to_string());
+    }
+}
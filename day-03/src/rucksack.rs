@@ -0,0 +1,259 @@
+use std::ops::{BitAnd, BitOr};
+
+use crate::Error;
+
+/// Priority of an item type: `a` through `z` are 1 through 26, `A` through `Z` 27 through 52.
+pub const fn priority(item: char) -> Option<u32> {
+    match item {
+        'a'..='z' => Some(item as u32 - 'a' as u32 + 1),
+        'A'..='Z' => Some(item as u32 - 'A' as u32 + 27),
+        _ => None,
+    }
+}
+
+/// Item type with the given priority.
+pub fn item(priority: u32) -> Option<char> {
+    match priority {
+        1..=26 => char::from_u32('a' as u32 + priority - 1),
+        27..=52 => char::from_u32('A' as u32 + priority - 27),
+        _ => None,
+    }
+}
+
+/// Set of item types, as a bitmask indexed by priority.
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone, Hash)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub const EMPTY: ItemSet = ItemSet(0);
+    /// Every item type.
+    pub const ALL: ItemSet = ItemSet(((1 << 52) - 1) << 1);
+
+    /// Set of the items in `items`, or the byte offset of the first one that is not a letter.
+    pub fn of(items: &str) -> Result<ItemSet, usize> {
+        items
+            .char_indices()
+            .try_fold(ItemSet::EMPTY, |set, (idx, item)| {
+                let priority = priority(item).ok_or(idx)?;
+                Ok(ItemSet(set.0 | 1 << priority))
+            })
+    }
+
+    pub fn contains(self, item: char) -> bool {
+        priority(item).is_some_and(|priority| self.0 & 1 << priority != 0)
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Priorities of the items in the set, lowest first.
+    pub fn priorities(self) -> impl Iterator<Item = u32> {
+        (1..=52).filter(move |priority| self.0 & 1 << priority != 0)
+    }
+
+    pub fn items(self) -> impl Iterator<Item = char> {
+        self.priorities().filter_map(item)
+    }
+
+    /// The only item in the set, if it holds exactly one.
+    pub fn single(self) -> Option<char> {
+        if self.len() == 1 {
+            item(self.0.trailing_zeros())
+        } else {
+            None
+        }
+    }
+}
+
+impl BitAnd for ItemSet {
+    type Output = ItemSet;
+
+    fn bitand(self, rhs: ItemSet) -> ItemSet {
+        ItemSet(self.0 & rhs.0)
+    }
+}
+
+impl BitOr for ItemSet {
+    type Output = ItemSet;
+
+    fn bitor(self, rhs: ItemSet) -> ItemSet {
+        ItemSet(self.0 | rhs.0)
+    }
+}
+
+/// One elf's rucksack, with the item types of each compartment.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Rucksack<'a> {
+    pub items: &'a str,
+    left: ItemSet,
+    right: ItemSet,
+}
+
+impl<'a> Rucksack<'a> {
+    /// Splits `items` into two compartments, or returns the byte offset of an item that is
+    /// not a letter.
+    pub fn new(items: &'a str) -> Result<Self, usize> {
+        // Validating first keeps the split below on a char boundary, as letters are ASCII.
+        ItemSet::of(items)?;
+
+        let (left, right) = items.split_at(items.len() / 2);
+        Ok(Rucksack {
+            items,
+            left: ItemSet::of(left)?,
+            right: ItemSet::of(right)?,
+        })
+    }
+
+    pub fn compartments(&self) -> (ItemSet, ItemSet) {
+        (self.left, self.right)
+    }
+
+    /// Every item type in the rucksack.
+    pub fn contents(&self) -> ItemSet {
+        self.left | self.right
+    }
+
+    /// Item types found in both compartments.
+    pub fn misplaced(&self) -> ItemSet {
+        self.left & self.right
+    }
+
+    /// The single item type found in both compartments of this rucksack, on line `line`.
+    pub fn misplaced_item(&self, line: usize) -> Result<char, Error> {
+        if !self.items.len().is_multiple_of(2) {
+            return Err(Error::OddRucksack {
+                line,
+                len: self.items.len(),
+            });
+        }
+
+        let misplaced = self.misplaced();
+        misplaced.single().ok_or_else(|| {
+            let token = self.items.to_string();
+            if misplaced.is_empty() {
+                Error::NoCommonItem { line, token }
+            } else {
+                Error::MultipleCommonItems {
+                    line,
+                    token,
+                    items: misplaced.items().collect(),
+                }
+            }
+        })
+    }
+}
+
+/// Item types carried by every one of `rucksacks`.
+pub fn intersection<'r, 'a: 'r>(rucksacks: impl IntoIterator<Item = &'r Rucksack<'a>>) -> ItemSet {
+    rucksacks
+        .into_iter()
+        .fold(ItemSet::ALL, |common, rucksack| {
+            common & rucksack.contents()
+        })
+}
+
+/// Badge of each group of `n` consecutive rucksacks, i.e. the item type all of them carry.
+///
+/// # Panics
+///
+/// When `n` is zero.
+pub fn group_badges(rucksacks: &[Rucksack], n: usize) -> Result<Vec<char>, Error> {
+    assert!(n > 0, "groups need at least one rucksack");
+
+    rucksacks
+        .chunks(n)
+        .enumerate()
+        .map(|(group, members)| group_badge(members, group * n + 1, n))
+        .collect()
+}
+
+/// Badge of a group of `n` rucksacks starting on line `line`.
+pub fn group_badge(members: &[Rucksack], line: usize, n: usize) -> Result<char, Error> {
+    if members.len() != n {
+        return Err(Error::IncompleteGroup {
+            line,
+            size: members.len(),
+            expected: n,
+        });
+    }
+
+    let common = intersection(members);
+    common.single().ok_or_else(|| {
+        if common.is_empty() {
+            Error::NoBadge { line }
+        } else {
+            Error::MultipleBadges {
+                line,
+                badges: common.items().collect(),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Day03, EXAMPLE};
+    use aoc_core::Solution;
+
+    #[test]
+    fn priorities_round_trip() {
+        assert_eq!(priority('a'), Some(1));
+        assert_eq!(priority('Z'), Some(52));
+        assert_eq!(priority('1'), None);
+
+        for p in 1..=52 {
+            assert_eq!(item(p).and_then(priority), Some(p));
+        }
+        assert_eq!(item(0), None);
+        assert_eq!(item(53), None);
+    }
+
+    #[test]
+    fn item_sets() {
+        let set = ItemSet::of("abcA").unwrap();
+        assert_eq!(set.len(), 4);
+        assert!(set.contains('A') && !set.contains('B'));
+        assert_eq!(
+            (set & ItemSet::of("cAZ").unwrap())
+                .items()
+                .collect::<String>(),
+            "cA"
+        );
+        assert_eq!(ItemSet::of("ab-c"), Err(2));
+        assert_eq!(ItemSet::ALL.len(), 52);
+        assert_eq!(ItemSet::of("pp").unwrap().single(), Some('p'));
+    }
+
+    #[test]
+    fn rucksack_compartments() {
+        let rucksack = Rucksack::new("vJrwpWtwJgWrhcsFMMfFFhFp").unwrap();
+        assert_eq!(rucksack.misplaced().single(), Some('p'));
+        assert_eq!(Rucksack::new("ab?d"), Err(2));
+        assert_eq!(Rucksack::new("éa"), Err(0));
+    }
+
+    #[test]
+    fn badges_of_any_group_size() {
+        let rucksacks = Day03::parse(EXAMPLE).unwrap();
+        assert_eq!(group_badges(&rucksacks, 3), Ok(vec!['r', 'Z']));
+        assert!(matches!(
+            group_badges(&rucksacks, 1),
+            Err(Error::MultipleBadges { line: 1, .. })
+        ));
+        assert_eq!(group_badges(&rucksacks, 4), Err(Error::NoBadge { line: 1 }));
+        assert_eq!(
+            group_badges(&rucksacks[..5], 3),
+            Err(Error::IncompleteGroup {
+                line: 4,
+                size: 2,
+                expected: 3
+            })
+        );
+    }
+}
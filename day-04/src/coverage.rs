@@ -0,0 +1,234 @@
+//! Sweep-line analysis of how a set of assignments covers the sections.
+
+use crate::section::SectionRange;
+
+/// Run of consecutive sections assigned to the same number of elves.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Segment {
+    pub range: SectionRange,
+    pub elves: usize,
+}
+
+/// How much of one elf's assignment other elves clean as well.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Redundancy {
+    /// Position of the elf among the assignments, from 0.
+    pub elf: usize,
+    pub range: SectionRange,
+    /// Sections of `range` at least one other elf is assigned to.
+    pub shared: u128,
+}
+
+impl Redundancy {
+    /// Whether the elf could stay home without leaving a section uncleaned.
+    pub fn is_redundant(&self) -> bool {
+        self.shared == size(&self.range)
+    }
+}
+
+/// Assignments split into segments at every start and end.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Coverage {
+    elves: Vec<SectionRange>,
+    /// Sorted, disjoint segments from the first to the last assigned section, including
+    /// segments no elf is assigned to.
+    segments: Vec<Segment>,
+}
+
+impl Coverage {
+    /// Sweeps over the start and end of every assignment in `O(n log n)`.
+    pub fn new(assignments: impl IntoIterator<Item = SectionRange>) -> Self {
+        let elves: Vec<_> = assignments.into_iter().collect();
+
+        // Ends are exclusive, which needs one more bit than section IDs have.
+        let mut events: Vec<(u128, isize)> = elves
+            .iter()
+            .flat_map(|range| [(range.start() as u128, 1), (range.end() as u128 + 1, -1)])
+            .collect();
+        events.sort_unstable();
+
+        let mut segments = Vec::new();
+        let mut depth = 0_isize;
+        let mut events = events.into_iter().peekable();
+
+        while let Some((at, delta)) = events.next() {
+            depth += delta;
+            if events.peek().is_some_and(|(next, _)| *next == at) {
+                continue;
+            }
+
+            if let Some(&(next, _)) = events.peek() {
+                segments.push(Segment {
+                    range: range(at, next),
+                    elves: depth as usize,
+                });
+            }
+        }
+
+        Coverage { elves, segments }
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// Normalized set of sections at least one elf is assigned to: sorted, disjoint and with a
+    /// gap between consecutive ranges.
+    pub fn covered(&self) -> Vec<SectionRange> {
+        self.assigned_at_least(1)
+    }
+
+    /// Sections no elf is assigned to, between the first and last assigned one.
+    pub fn gaps(&self) -> Vec<SectionRange> {
+        self.segments
+            .iter()
+            .filter(|segment| segment.elves == 0)
+            .map(|segment| segment.range)
+            .collect()
+    }
+
+    /// Number of sections at least one elf is assigned to.
+    pub fn total(&self) -> u128 {
+        self.segments
+            .iter()
+            .filter(|segment| segment.elves > 0)
+            .map(|segment| size(&segment.range))
+            .sum()
+    }
+
+    /// Merged ranges of sections at least `elves` elves are assigned to.
+    pub fn assigned_at_least(&self, elves: usize) -> Vec<SectionRange> {
+        let mut merged: Vec<SectionRange> = Vec::new();
+
+        for segment in self.segments.iter().filter(|s| s.elves >= elves.max(1)) {
+            match merged
+                .last_mut()
+                .and_then(|last| Some((last.union(&segment.range)?, last)))
+            {
+                Some((union, last)) => *last = union,
+                None => merged.push(segment.range),
+            }
+        }
+
+        merged
+    }
+
+    /// The first run of sections assigned to the most elves.
+    pub fn most_assigned(&self) -> Option<Segment> {
+        self.segments
+            .iter()
+            .filter(|segment| segment.elves > 0)
+            .rev()
+            .max_by_key(|segment| segment.elves)
+            .copied()
+    }
+
+    /// Shared sections of every elf's assignment, in assignment order.
+    pub fn redundancy(&self) -> Vec<Redundancy> {
+        // Every assignment starts and ends on segment boundaries, so prefix sums over the
+        // segments give each elf's shared sections with two binary searches.
+        let mut shared = vec![0_u128];
+        for segment in &self.segments {
+            let size = if segment.elves > 1 {
+                size(&segment.range)
+            } else {
+                0
+            };
+            shared.push(shared.last().unwrap() + size);
+        }
+
+        self.elves
+            .iter()
+            .enumerate()
+            .map(|(elf, range)| {
+                let first = self
+                    .segments
+                    .partition_point(|s| s.range.start() < range.start());
+                let last = self
+                    .segments
+                    .partition_point(|s| s.range.end() <= range.end());
+
+                Redundancy {
+                    elf,
+                    range: *range,
+                    shared: shared[last] - shared[first],
+                }
+            })
+            .collect()
+    }
+}
+
+/// Range of sections from `start` up to `end`, exclusive.
+fn range(start: u128, end: u128) -> SectionRange {
+    SectionRange::new(start as u64, (end - 1) as u64).expect("segments are not empty")
+}
+
+fn size(range: &SectionRange) -> u128 {
+    (range.end() - range.start()) as u128 + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(start: u64, end: u64) -> SectionRange {
+        SectionRange::new(start, end).unwrap()
+    }
+
+    #[test]
+    fn gaps_and_overlaps() {
+        let coverage = Coverage::new([range(1, 3), range(5, 6), range(6, 10), range(12, 12)]);
+
+        assert_eq!(
+            coverage.covered(),
+            [range(1, 3), range(5, 10), range(12, 12)]
+        );
+        assert_eq!(coverage.gaps(), [range(4, 4), range(11, 11)]);
+        assert_eq!(coverage.total(), 10);
+        assert_eq!(
+            coverage.most_assigned(),
+            Some(Segment {
+                range: range(6, 6),
+                elves: 2
+            })
+        );
+        assert_eq!(coverage.assigned_at_least(2), [range(6, 6)]);
+
+        let shared: Vec<_> = coverage.redundancy().iter().map(|r| r.shared).collect();
+        assert_eq!(shared, [0, 1, 1, 0]);
+    }
+
+    #[test]
+    fn redundant_elves() {
+        let coverage = Coverage::new([range(2, 8), range(3, 7), range(8, 9)]);
+        let redundant: Vec<_> = coverage
+            .redundancy()
+            .iter()
+            .filter(|r| r.is_redundant())
+            .map(|r| r.elf)
+            .collect();
+
+        assert_eq!(redundant, [1]);
+        assert_eq!(coverage.assigned_at_least(2), [range(3, 8)]);
+    }
+
+    #[test]
+    fn extreme_sections() {
+        let coverage = Coverage::new([range(0, u64::MAX), range(u64::MAX, u64::MAX)]);
+
+        assert_eq!(coverage.total(), u64::MAX as u128 + 1);
+        assert!(coverage.gaps().is_empty());
+        assert_eq!(
+            coverage.most_assigned().unwrap().range,
+            range(u64::MAX, u64::MAX)
+        );
+    }
+
+    #[test]
+    fn no_assignments() {
+        let coverage = Coverage::new([]);
+        assert!(coverage.covered().is_empty());
+        assert_eq!(coverage.total(), 0);
+        assert_eq!(coverage.most_assigned(), None);
+    }
+}
@@ -0,0 +1,38 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Assignment list with `pairs` lines of section ranges between 1 and 99.
+pub fn input(seed: u64, pairs: usize) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    (0..pairs)
+        .map(|_| {
+            let first = range(&mut rng);
+            let second = range(&mut rng);
+
+            format!("{}-{},{}-{}", first.0, first.1, second.0, second.1)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn range(rng: &mut StdRng) -> (u32, u32) {
+    let start = rng.gen_range(1..=99);
+    let end = rng.gen_range(start..=99);
+
+    (start, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn generated_input_is_valid() {
+        let generated = input(4, 500);
+        assert_eq!(generated, input(4, 500));
+        assert!(part1(&generated).is_ok());
+        assert!(part2(&generated).is_ok());
+    }
+}
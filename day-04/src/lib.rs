@@ -1,61 +1,198 @@
-use std::collections::HashSet;
+pub mod coverage;
+pub mod generate;
+#[cfg(test)]
+mod reference;
+pub mod section;
+
+use aoc_core::{Part, Position, Solution};
+use thiserror::Error;
+
+use crate::section::SectionRange;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum Error {
+    #[error("invalid section {token:?} at {position}")]
+    InvalidSection { position: Position, token: String },
+    #[error("range {token:?} at {position} ends before it starts")]
+    InvertedRange { position: Position, token: String },
+    #[error("expected a pair of ranges in {token:?} at {position}")]
+    MalformedPair { position: Position, token: String },
+}
+
+/// Example from the puzzle description.
+pub const EXAMPLE: &str = "2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8";
 
-pub fn part1(input: &str) -> String {
-    count_overlaps(input, full_overlap)
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Camp Cleanup";
+
+    type Input<'a> = Vec<(SectionRange, SectionRange)>;
+    type Output1 = usize;
+    type Output2 = usize;
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        input
+            .lines()
+            .map(|line| {
+                let (first, second) = line.split_once(',').ok_or_else(|| Error::MalformedPair {
+                    position: Position::of(input, line),
+                    token: line.to_string(),
+                })?;
+
+                Ok((range(input, first)?, range(input, second)?))
+            })
+            .collect()
+    }
+
+    fn part1(pairs: &Self::Input<'_>) -> Result<Self::Output1, Self::Error> {
+        Ok(count_pairs(pairs, OverlapKind::Nested))
+    }
+
+    fn part2(pairs: &Self::Input<'_>) -> Result<Self::Output2, Self::Error> {
+        Ok(count_pairs(pairs, OverlapKind::Overlaps))
+    }
 }
 
-pub fn part2(input: &str) -> String {
-    count_overlaps(input, partial_overlap)
+pub fn part1(input: &str) -> Result<String, Error> {
+    Day04::solve(Part::One, input)
 }
 
-fn count_overlaps(input: &str, p: impl Fn(HashSet<u32>, HashSet<u32>) -> bool) -> String {
-    let result = input
-        .lines()
-        .filter(|line| {
-            let numbers = line
-                .split(&['-', ','])
-                .map(|s| s.parse::<u32>().unwrap())
-                .collect::<Vec<_>>();
+pub fn part2(input: &str) -> Result<String, Error> {
+    Day04::solve(Part::Two, input)
+}
 
-            let first = (numbers[0]..=numbers[1]).collect::<HashSet<_>>();
-            let second = (numbers[2]..=numbers[3]).collect::<HashSet<_>>();
+fn range(input: &str, token: &str) -> Result<SectionRange, Error> {
+    let (start, end) = token.split_once('-').ok_or_else(|| Error::MalformedPair {
+        position: Position::of(input, token),
+        token: token.to_string(),
+    })?;
 
-            let full_overlap = p(first, second);
-            full_overlap
+    let section = |s: &str| {
+        s.parse::<u64>().map_err(|_| Error::InvalidSection {
+            position: Position::of(input, s),
+            token: s.to_string(),
         })
-        .count();
+    };
 
-    result.to_string()
+    SectionRange::new(section(start)?, section(end)?).ok_or_else(|| Error::InvertedRange {
+        position: Position::of(input, token),
+        token: token.to_string(),
+    })
 }
 
-fn full_overlap(first: HashSet<u32>, second: HashSet<u32>) -> bool {
-    first.iter().all(|i| second.contains(i)) || second.iter().all(|i| first.contains(i))
+/// Relation between the first and second assignment of a pair.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum OverlapKind {
+    /// The first contains every section of the second.
+    Contains,
+    /// The second contains every section of the first.
+    ContainedBy,
+    /// Either contains the other, as counted by the first part.
+    Nested,
+    /// They share at least one section, as counted by the second part.
+    Overlaps,
+    /// They share no section and leave none between them.
+    Adjacent,
+    /// They share no section.
+    Disjoint,
+    /// They share at least this many sections.
+    OverlapAtLeast(u64),
 }
 
-fn partial_overlap(first: HashSet<u32>, second: HashSet<u32>) -> bool {
-    first.iter().any(|i| second.contains(i)) || second.iter().any(|i| first.contains(i))
+impl OverlapKind {
+    pub fn matches(self, first: &SectionRange, second: &SectionRange) -> bool {
+        match self {
+            OverlapKind::Contains => first.contains(second),
+            OverlapKind::ContainedBy => second.contains(first),
+            OverlapKind::Nested => first.contains(second) || second.contains(first),
+            OverlapKind::Overlaps => first.overlaps(second),
+            OverlapKind::Adjacent => first.is_adjacent(second),
+            OverlapKind::Disjoint => !first.overlaps(second),
+            OverlapKind::OverlapAtLeast(n) => first
+                .intersection(second)
+                .map_or(n == 0, |common| common.len() >= n),
+        }
+    }
+}
+
+/// Number of pairs whose assignments are related as `kind`.
+pub fn count_pairs(pairs: &[(SectionRange, SectionRange)], kind: OverlapKind) -> usize {
+    pairs
+        .iter()
+        .filter(|(first, second)| kind.matches(first, second))
+        .count()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &str = "2-4,6-8
-2-3,4-5
-5-7,7-9
-2-8,3-7
-6-6,4-6
-2-6,4-8";
-
     #[test]
     fn part1_works() {
-        let result = part1(INPUT);
+        let result = part1(EXAMPLE).unwrap();
         assert_eq!(result, "2");
     }
 
     #[test]
     fn part2_works() {
-        let result = part2(INPUT);
+        let result = part2(EXAMPLE).unwrap();
         assert_eq!(result, "4");
     }
+
+    #[test]
+    fn invalid_section_is_located() {
+        let result = part1("2-4,6-8\n2-3,x-5");
+        assert_eq!(
+            result,
+            Err(Error::InvalidSection {
+                position: Position { line: 2, column: 5 },
+                token: "x".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn inverted_range_is_located() {
+        let result = part1("2-4,8-6");
+        assert_eq!(
+            result,
+            Err(Error::InvertedRange {
+                position: Position { line: 1, column: 5 },
+                token: "8-6".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn overlap_kinds() {
+        let pairs = Day04::parse(EXAMPLE).unwrap();
+        let count = |kind| count_pairs(&pairs, kind);
+
+        assert_eq!(count(OverlapKind::Contains), 1);
+        assert_eq!(count(OverlapKind::ContainedBy), 1);
+        assert_eq!(count(OverlapKind::Nested), 2);
+        assert_eq!(count(OverlapKind::Overlaps), 4);
+        assert_eq!(count(OverlapKind::Disjoint), 2);
+        assert_eq!(count(OverlapKind::Adjacent), 1);
+        assert_eq!(count(OverlapKind::OverlapAtLeast(0)), 6);
+        assert_eq!(count(OverlapKind::OverlapAtLeast(1)), 4);
+        assert_eq!(count(OverlapKind::OverlapAtLeast(3)), 2);
+        assert_eq!(count(OverlapKind::OverlapAtLeast(6)), 0);
+    }
+
+    #[test]
+    fn huge_sections() {
+        let input = "1-18446744073709551615,9000000000000-9000000000001\n\
+                     5-10000000000000000000,10000000000000000000-10000000000000000001";
+        assert_eq!(part1(input).unwrap(), "1");
+        assert_eq!(part2(input).unwrap(), "2");
+    }
 }
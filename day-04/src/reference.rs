@@ -0,0 +1,83 @@
+use proptest::prelude::*;
+
+use aoc_core::Solution;
+
+use crate::coverage::Coverage;
+use crate::{generate, part1, part2, Day04};
+
+/// Counts pairs for which `p` holds, checking it section by section.
+fn count(input: &str, p: impl Fn(&[u32], &[u32]) -> bool) -> usize {
+    input
+        .lines()
+        .filter(|line| {
+            let n = line
+                .split(['-', ','])
+                .map(|s| s.parse::<u32>().unwrap())
+                .collect::<Vec<_>>();
+            let first = (n[0]..=n[1]).collect::<Vec<_>>();
+            let second = (n[2]..=n[3]).collect::<Vec<_>>();
+
+            p(&first, &second)
+        })
+        .count()
+}
+
+fn contains(outer: &[u32], inner: &[u32]) -> bool {
+    inner.iter().all(|section| outer.contains(section))
+}
+
+proptest! {
+    #[test]
+    fn part1_matches_reference(seed in any::<u64>(), pairs in 1..300usize) {
+        let input = generate::input(seed, pairs);
+        let expected = count(&input, |a, b| contains(a, b) || contains(b, a));
+        prop_assert_eq!(part1(&input).unwrap(), expected.to_string());
+    }
+
+    #[test]
+    fn part2_matches_reference(seed in any::<u64>(), pairs in 1..300usize) {
+        let input = generate::input(seed, pairs);
+        let expected = count(&input, |a, b| a.iter().any(|section| b.contains(section)));
+        prop_assert_eq!(part2(&input).unwrap(), expected.to_string());
+    }
+
+    #[test]
+    fn coverage_matches_reference(seed in any::<u64>(), pairs in 1..100usize) {
+        let input = generate::input(seed, pairs);
+        let elves: Vec<_> = Day04::parse(&input)
+            .unwrap()
+            .into_iter()
+            .flat_map(|(first, second)| [first, second])
+            .collect();
+        let coverage = Coverage::new(elves.iter().copied());
+
+        // Number of elves assigned to each section, by ID.
+        let mut depth = [0_usize; 101];
+        for elf in &elves {
+            for section in elf.start()..=elf.end() {
+                depth[section as usize] += 1;
+            }
+        }
+        let first = depth.iter().position(|d| *d > 0).unwrap();
+        let last = depth.iter().rposition(|d| *d > 0).unwrap();
+
+        let total = depth.iter().filter(|d| **d > 0).count();
+        prop_assert_eq!(coverage.total(), total as u128);
+
+        let gaps: u64 = coverage.gaps().iter().map(|gap| gap.len()).sum();
+        let uncovered = depth[first..=last].iter().filter(|d| **d == 0).count();
+        prop_assert_eq!(gaps, uncovered as u64);
+
+        let most = coverage.most_assigned().unwrap();
+        prop_assert_eq!(most.elves, *depth.iter().max().unwrap());
+        prop_assert_eq!(depth[most.range.start() as usize], most.elves);
+
+        for redundancy in coverage.redundancy() {
+            let range = redundancy.range;
+            let shared = (range.start()..=range.end())
+                .filter(|section| depth[*section as usize] > 1)
+                .count();
+            prop_assert_eq!(redundancy.shared, shared as u128);
+        }
+    }
+}
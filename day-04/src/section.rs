@@ -0,0 +1,118 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// Inclusive range of section IDs, never empty.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash)]
+pub struct SectionRange {
+    start: u64,
+    end: u64,
+}
+
+impl SectionRange {
+    /// Range from `start` to `end` inclusive, or `None` if `start` comes after `end`.
+    pub fn new(start: u64, end: u64) -> Option<Self> {
+        (start <= end).then_some(SectionRange { start, end })
+    }
+
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    pub fn end(&self) -> u64 {
+        self.end
+    }
+
+    /// Number of sections, saturating at `u64::MAX` for the range of every ID.
+    pub fn len(&self) -> u64 {
+        (self.end - self.start).saturating_add(1)
+    }
+
+    /// Always false, as a range holds at least one section.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    pub fn contains_section(&self, section: u64) -> bool {
+        self.start <= section && section <= self.end
+    }
+
+    /// Whether every section of `other` is in this range.
+    pub fn contains(&self, other: &SectionRange) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    /// Whether the ranges share at least one section.
+    pub fn overlaps(&self, other: &SectionRange) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// Whether the ranges are disjoint but leave no section between them.
+    pub fn is_adjacent(&self, other: &SectionRange) -> bool {
+        self.end.checked_add(1) == Some(other.start) || other.end.checked_add(1) == Some(self.start)
+    }
+
+    pub fn intersection(&self, other: &SectionRange) -> Option<SectionRange> {
+        SectionRange::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// Range covering both, if they overlap or are adjacent so that the result has no gap.
+    pub fn union(&self, other: &SectionRange) -> Option<SectionRange> {
+        (self.overlaps(other) || self.is_adjacent(other)).then(|| SectionRange {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        })
+    }
+}
+
+impl Display for SectionRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(start: u64, end: u64) -> SectionRange {
+        SectionRange::new(start, end).unwrap()
+    }
+
+    #[test]
+    fn relations() {
+        let outer = range(2, 8);
+        let inner = range(3, 7);
+        let right = range(7, 9);
+        let next = range(9, 12);
+
+        assert!(outer.contains(&inner) && !inner.contains(&outer));
+        assert!(outer.overlaps(&right) && right.overlaps(&outer));
+        assert!(!outer.overlaps(&next));
+        assert!(outer.is_adjacent(&next) && next.is_adjacent(&outer));
+        assert!(!inner.is_adjacent(&next));
+        assert!(outer.contains_section(8) && !outer.contains_section(9));
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(range(2, 8).intersection(&range(7, 9)), Some(range(7, 8)));
+        assert_eq!(range(2, 4).intersection(&range(6, 8)), None);
+        assert_eq!(range(2, 4).union(&range(5, 8)), Some(range(2, 8)));
+        assert_eq!(range(2, 4).union(&range(6, 8)), None);
+        assert_eq!(range(6, 6).len(), 1);
+        assert_eq!(SectionRange::new(5, 4), None);
+    }
+
+    #[test]
+    fn huge_ranges() {
+        let all = range(0, u64::MAX);
+        let top = range(u64::MAX - 10, u64::MAX);
+
+        assert_eq!(all.len(), u64::MAX);
+        assert_eq!(top.len(), 11);
+        assert!(all.contains(&top));
+        assert_eq!(all.intersection(&top), Some(top));
+        assert!(!top.is_adjacent(&range(0, 5)));
+        assert_eq!(range(1, 1 << 40).len(), 1 << 40);
+    }
+}
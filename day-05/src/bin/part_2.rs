@@ -1,7 +1,17 @@
-use day_05::part2;
-use std::fs;
+use std::env;
+use std::process::ExitCode;
 
-fn main() {
-    let file = fs::read_to_string("./input.txt").unwrap();
-    println!("{}", part2(&file));
+use aoc_core::input;
+use day_05::{part2, part2_lenient};
+
+fn main() -> ExitCode {
+    let mut args = env::args().skip(1).collect::<Vec<_>>();
+    let lenient = input::take_flag(&mut args, "--lenient");
+    let paths = input::paths(args, "./input.txt");
+
+    if lenient {
+        input::solve_all_with_warnings(&paths, part2_lenient)
+    } else {
+        input::solve_all(&paths, part2)
+    }
 }
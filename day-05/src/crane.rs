@@ -0,0 +1,79 @@
+//! Crane models, differing only in how many crates they pick up at once.
+//!
+//! A move of `amount` crates is carried out as a series of lifts. Each lift takes crates
+//! from the top of the source stack and sets them down on the destination stack in the
+//! same order, so a crane lifting one crate at a time reverses the moved crates while one
+//! lifting all of them keeps their order.
+
+/// Rule deciding how many crates a crane picks up per lift.
+pub trait Crane {
+    /// Number of crates picked up when `remaining` (at least one) are still to be moved.
+    ///
+    /// Values outside `1..=remaining` are clamped into that range.
+    fn lift(&self, remaining: usize) -> usize;
+}
+
+impl<C: Crane + ?Sized> Crane for &C {
+    fn lift(&self, remaining: usize) -> usize {
+        (**self).lift(remaining)
+    }
+}
+
+/// Moves crates one at a time, as in the first part.
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn lift(&self, _remaining: usize) -> usize {
+        1
+    }
+}
+
+/// Moves all crates of a step at once, as in the second part.
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn lift(&self, remaining: usize) -> usize {
+        remaining
+    }
+}
+
+/// Moves at most `capacity` crates per lift.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct BatchCrane {
+    capacity: usize,
+}
+
+impl BatchCrane {
+    /// A crane lifting up to `capacity` crates, or `None` if it cannot lift any.
+    pub fn new(capacity: usize) -> Option<Self> {
+        (capacity > 0).then_some(BatchCrane { capacity })
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl Crane for BatchCrane {
+    fn lift(&self, remaining: usize) -> usize {
+        self.capacity.min(remaining)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lift_sizes() {
+        assert_eq!(CrateMover9000.lift(5), 1);
+        assert_eq!(CrateMover9001.lift(5), 5);
+
+        let crane = BatchCrane::new(2).unwrap();
+        assert_eq!(crane.lift(5), 2);
+        assert_eq!(crane.lift(1), 1);
+        assert_eq!(BatchCrane::new(0), None);
+    }
+}
@@ -0,0 +1,125 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+/// Shape of a generated crate drawing.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Layout {
+    pub stacks: usize,
+    /// Height of the tallest stack.
+    pub height: usize,
+    /// Number of letters in each crate label.
+    pub label: usize,
+}
+
+impl Layout {
+    /// Nine stacks of single-letter crates, as in the puzzle input.
+    pub const PUZZLE: Layout = Layout {
+        stacks: 9,
+        height: 8,
+        label: 1,
+    };
+}
+
+/// Crate drawing of nine stacks followed by `moves` rearrangement steps.
+///
+/// Both crane models leave the stacks at the same heights, so every move is valid
+/// for either of them.
+pub fn input(seed: u64, moves: usize) -> String {
+    yard(seed, Layout::PUZZLE, moves)
+}
+
+/// Crate drawing shaped like `layout` followed by `moves` rearrangement steps.
+///
+/// # Panics
+///
+/// When `layout` has fewer than two stacks, or no height or label width.
+pub fn yard(seed: u64, layout: Layout, moves: usize) -> String {
+    let Layout {
+        stacks,
+        height,
+        label,
+    } = layout;
+    assert!(stacks > 1 && height > 0 && label > 0, "degenerate layout");
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let letters = ('A'..='Z').collect::<Vec<_>>();
+
+    let mut heights = (0..stacks)
+        .map(|_| rng.gen_range(1..=height))
+        .collect::<Vec<_>>();
+    heights[rng.gen_range(0..stacks)] = height;
+
+    let mut lines = (0..height)
+        .rev()
+        .map(|row| {
+            heights
+                .iter()
+                .map(|&stack| {
+                    if row < stack {
+                        let name = (0..label)
+                            .map(|_| letters.choose(&mut rng).unwrap())
+                            .collect::<String>();
+                        format!("[{name}]")
+                    } else {
+                        " ".repeat(label + 2)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>();
+
+    lines.push(
+        (1..=stacks)
+            .map(|id| format!("{id:^0$}", label + 2))
+            .collect::<Vec<_>>()
+            .join(" "),
+    );
+    lines.push(String::new());
+
+    for _ in 0..moves {
+        let from = loop {
+            let from = rng.gen_range(0..stacks);
+            if heights[from] > 0 {
+                break from;
+            }
+        };
+        let to = (from + rng.gen_range(1..stacks)) % stacks;
+        let amount = rng.gen_range(1..=heights[from]);
+
+        heights[from] -= amount;
+        heights[to] += amount;
+
+        lines.push(format!("move {amount} from {} to {}", from + 1, to + 1));
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn generated_input_is_valid() {
+        let generated = input(5, 500);
+        assert_eq!(generated, input(5, 500));
+        assert!(part1(&generated).is_ok());
+        assert!(part2(&generated).is_ok());
+    }
+
+    #[test]
+    fn large_yards_are_valid() {
+        let layout = Layout {
+            stacks: 12,
+            height: 20,
+            label: 3,
+        };
+        let generated = yard(5, layout, 500);
+        assert!(generated.contains(" 11    12  \n"));
+        assert!(part1(&generated).is_ok());
+        assert!(part2(&generated).is_ok());
+    }
+}
@@ -1,74 +1,248 @@
-use nom::branch::alt;
+pub mod crane;
+pub mod generate;
+#[cfg(test)]
+mod reference;
+pub mod render;
+pub mod reverse;
+pub mod yard;
+
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
 use nom::bytes::complete::tag;
 use nom::character::complete;
-use nom::character::complete::{alpha1, digit1, multispace1, newline, space1};
-use nom::multi::{many1, separated_list1};
-use nom::sequence::{delimited, preceded};
+use nom::character::complete::{multispace1, newline, not_line_ending, space0, space1};
+use nom::combinator::verify;
+use nom::error::ErrorKind;
+use nom::multi::{many0, separated_list1};
+use nom::sequence::{delimited, terminated};
 use nom::IResult;
+use thiserror::Error;
+
+use aoc_core::{parse_all, Part, Position, Solution, SyntaxError};
+
+use crate::crane::{Crane, CrateMover9000, CrateMover9001};
+use crate::yard::Yard;
+
+/// Crate labels per stack, bottom first.
+pub type Stacks<'a> = Vec<Vec<&'a str>>;
+
+/// One rearrangement step, with stacks numbered from 1 as in the drawing.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Move {
+    pub amount: u32,
+    pub from: u32,
+    pub to: u32,
+    /// Line of the step in the puzzle input.
+    pub line: usize,
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "move {} from {} to {}", self.amount, self.from, self.to)
+    }
+}
 
-pub fn part1(input: &str) -> String {
-    let (_, (mut stacks, moves)) = crates(input).unwrap();
-
-    for (amount, from, to) in moves {
-        let len = stacks[from as usize].len();
-        let drained = stacks[from as usize]
-            .drain((len - (amount as usize))..)
-            .rev()
-            .collect::<Vec<_>>();
-        for crt in drained {
-            stacks[to as usize].push(crt);
+#[derive(Debug, Error, PartialEq)]
+pub enum Error {
+    #[error(transparent)]
+    Syntax(#[from] SyntaxError),
+    #[error("move on line {line} uses stack {stack}, but stacks are numbered 1 to {stacks}")]
+    NoSuchStack {
+        line: usize,
+        stack: u32,
+        stacks: usize,
+    },
+    #[error("move on line {line} takes crates from empty stack {stack}")]
+    EmptyStack { line: usize, stack: u32 },
+    #[error("move on line {line} takes {amount} crates from stack {stack}, which holds {height}")]
+    NotEnoughCrates {
+        line: usize,
+        stack: u32,
+        amount: u32,
+        height: usize,
+    },
+}
+
+impl Error {
+    /// Line of the input the error was found on.
+    pub fn line(&self) -> usize {
+        match self {
+            Error::Syntax(e) => e.position.line,
+            Error::NoSuchStack { line, .. }
+            | Error::EmptyStack { line, .. }
+            | Error::NotEnoughCrates { line, .. } => *line,
         }
     }
+}
 
-    let top = stacks
-        .iter()
-        .map(|stack| *stack.last().unwrap_or(&""))
-        .collect::<String>();
+/// Example from the puzzle description.
+pub const EXAMPLE: &str = "    [D]
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
 
-    top
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2";
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "Supply Stacks";
+
+    type Input<'a> = (Stacks<'a>, Vec<Move>);
+    type Output1 = String;
+    type Output2 = String;
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        Ok(parse_all(input, crates(input))?)
+    }
+
+    fn part1((stacks, moves): &Self::Input<'_>) -> Result<Self::Output1, Self::Error> {
+        rearrange(stacks, moves, CrateMover9000)
+    }
+
+    fn part2((stacks, moves): &Self::Input<'_>) -> Result<Self::Output2, Self::Error> {
+        rearrange(stacks, moves, CrateMover9001)
+    }
 }
 
-pub fn part2(input: &str) -> String {
-    let (_, (mut stacks, moves)) = crates(input).unwrap();
+/// Top crates once `crane` has carried out every move, stopping at the first invalid one.
+pub fn rearrange(stacks: &Stacks, moves: &[Move], crane: impl Crane) -> Result<String, Error> {
+    let mut yard = Yard::new(stacks.clone(), crane);
+    for &step in moves {
+        yard.apply(step)?;
+    }
 
-    for (amount, from, to) in moves {
-        let len = stacks[from as usize].len();
-        let drained = stacks[from as usize]
-            .drain((len - (amount as usize))..)
-            .collect::<Vec<_>>();
-        for crt in drained {
-            stacks[to as usize].push(crt);
-        }
+    Ok(yard.top())
+}
+
+/// A move passed over in lenient mode, displayed as a warning naming why.
+#[derive(Debug, PartialEq)]
+pub struct Skipped(pub Error);
+
+impl Display for Skipped {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "skipped: {}", self.0)
     }
+}
 
-    let top = stacks
+/// Top crates once `crane` has carried out every valid move, with the moves it skipped.
+pub fn rearrange_lenient(
+    stacks: &Stacks,
+    moves: &[Move],
+    crane: impl Crane,
+) -> (String, Vec<Skipped>) {
+    let mut yard = Yard::new(stacks.clone(), crane);
+    let skipped = moves
         .iter()
-        .map(|stack| *stack.last().unwrap_or(&""))
-        .collect::<String>();
+        .filter_map(|&step| yard.apply(step).err().map(Skipped))
+        .collect();
+
+    (yard.top(), skipped)
+}
+
+pub fn part1(input: &str) -> Result<String, Error> {
+    Day05::solve(Part::One, input)
+}
 
-    top
+pub fn part2(input: &str) -> Result<String, Error> {
+    Day05::solve(Part::Two, input)
 }
 
-fn parse_crate(input: &str) -> IResult<&str, Option<&str>> {
-    let (input, c) = alt((
-        tag("   "),
-        delimited(complete::char('['), alpha1, complete::char(']')),
-    ))(input)?;
+/// Like [`part1`], but skips invalid moves and returns them alongside the answer.
+pub fn part1_lenient(input: &str) -> Result<(String, Vec<Skipped>), Error> {
+    let (stacks, moves) = Day05::parse(input)?;
+    Ok(rearrange_lenient(&stacks, &moves, CrateMover9000))
+}
 
-    let result = match c {
-        "   " => None,
-        value => Some(value),
-    };
+/// Like [`part2`], but skips invalid moves and returns them alongside the answer.
+pub fn part2_lenient(input: &str) -> Result<(String, Vec<Skipped>), Error> {
+    let (stacks, moves) = Day05::parse(input)?;
+    Ok(rearrange_lenient(&stacks, &moves, CrateMover9001))
+}
 
-    Ok((input, result))
+/// A line of the drawing holding at least one crate.
+fn row(input: &str) -> IResult<&str, &str> {
+    verify(not_line_ending, |line: &str| {
+        line.trim_start().starts_with('[')
+    })(input)
 }
 
-fn line(input: &str) -> IResult<&str, Vec<Option<&str>>> {
-    let (input, result) = separated_list1(tag(" "), parse_crate)(input)?;
+/// The stack numbers below the drawing, which must count up from 1.
+fn footer(input: &str) -> IResult<&str, usize> {
+    let (rest, numbers) = delimited(space0, separated_list1(space1, complete::u32), space0)(input)?;
+
+    if (1..)
+        .zip(&numbers)
+        .any(|(expected, &number)| number != expected)
+    {
+        return Err(failure(input));
+    }
+
+    Ok((rest, numbers.len()))
+}
 
-    Ok((input, result))
+fn failure(input: &str) -> nom::Err<nom::error::Error<&str>> {
+    nom::Err::Failure(nom::error::Error::new(input, ErrorKind::Verify))
 }
 
+/// Reads the crates of `rows`, top row first, into `count` stacks.
+///
+/// Cells are as wide as the first crate found, with a single space between them, so labels
+/// can be of any width as long as every cell shares it. Widths are counted in characters.
+fn stacks<'a>(
+    rows: &[&'a str],
+    count: usize,
+) -> Result<Stacks<'a>, nom::Err<nom::error::Error<&'a str>>> {
+    let width = rows
+        .iter()
+        .find_map(|row| {
+            let open = row.find('[')?;
+            Some(row[open..].chars().position(|c| c == ']')? + 1)
+        })
+        .unwrap_or(3);
+
+    let mut stacks: Stacks = vec![vec![]; count];
+
+    for row in rows.iter().rev() {
+        // Byte offset of every character, and of the end of the row.
+        let bounds: Vec<_> = row
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .chain([row.len()])
+            .collect();
+        let chars = bounds.len() - 1;
+
+        for (idx, start) in (0..chars).step_by(width + 1).enumerate() {
+            let end = chars.min(start + width);
+            let cell = &row[bounds[start]..bounds[end]];
+            let label = if cell.trim().is_empty() {
+                continue;
+            } else if end - start == width && idx < count {
+                cell.strip_prefix('[')
+                    .and_then(|cell| cell.strip_suffix(']'))
+                    .map(str::trim)
+                    .filter(|label| !label.is_empty())
+            } else {
+                None
+            };
+
+            match label {
+                Some(label) => stacks[idx].push(label),
+                None => return Err(failure(&row[bounds[start]..])),
+            }
+        }
+    }
+
+    Ok(stacks)
+}
+
+/// `(amount, from, to)` of a move, which is only checked against the stacks when applied.
 fn move_crate(input: &str) -> IResult<&str, (u32, u32, u32)> {
     let (input, _) = tag("move ")(input)?;
     let (input, amount) = complete::u32(input)?;
@@ -77,55 +251,199 @@ fn move_crate(input: &str) -> IResult<&str, (u32, u32, u32)> {
     let (input, _) = tag(" to ")(input)?;
     let (input, to) = complete::u32(input)?;
 
-    Ok((input, (amount, from - 1, to - 1)))
+    Ok((input, (amount, from, to)))
 }
 
-fn crates(input: &str) -> IResult<&str, (Vec<Vec<&str>>, Vec<(u32, u32, u32)>)> {
-    let (input, horizontal_crates) = separated_list1(newline, line)(input)?;
-    let (input, _) = newline(input)?;
-    let (input, _numbers) = many1(preceded(space1, digit1))(input)?;
+fn crates(drawing: &str) -> IResult<&str, (Stacks<'_>, Vec<Move>)> {
+    let (input, rows) = many0(terminated(row, newline))(drawing)?;
+    let (input, count) = footer(input)?;
+    let stacks = stacks(&rows, count)?;
     let (input, _) = multispace1(input)?;
+    let first_line = Position::of(drawing, input).line;
     let (input, moves) = separated_list1(newline, move_crate)(input)?;
 
-    let mut vertical_crates: Vec<Vec<&str>> = vec![];
-    for _ in 0..=horizontal_crates.len() {
-        vertical_crates.push(vec![]);
-    }
+    let moves = moves
+        .into_iter()
+        .enumerate()
+        .map(|(idx, (amount, from, to))| Move {
+            amount,
+            from,
+            to,
+            line: first_line + idx,
+        })
+        .collect();
 
-    for row in horizontal_crates.iter().rev() {
-        for (id, value) in row.iter().enumerate() {
-            if let Some(value) = *value {
-                vertical_crates[id].push(value)
-            }
-        }
-    }
-
-    Ok((input, (vertical_crates, moves)))
+    Ok((input, (stacks, moves)))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &str = "    [D]
-[N] [C]    
-[Z] [M] [P]
- 1   2   3 
-
-move 1 from 2 to 1
-move 3 from 1 to 3
-move 2 from 2 to 1
-move 1 from 1 to 2";
-
     #[test]
     fn part1_works() {
-        let result = part1(INPUT);
+        let result = part1(EXAMPLE).unwrap();
         assert_eq!(result, "CMZ");
     }
 
     #[test]
     fn part2_works() {
-        let result = part2(INPUT);
+        let result = part2(EXAMPLE).unwrap();
         assert_eq!(result, "MCD");
     }
+
+    #[test]
+    fn malformed_move_is_located() {
+        let input = EXAMPLE.replace("move 2 from 2 to 1", "move 2 fro 2 to 1");
+        let result = part1(&input);
+        assert_eq!(
+            result,
+            Err(Error::Syntax(SyntaxError {
+                position: Position { line: 8, column: 1 },
+                token: "move 2 fro 2 to 1".to_string(),
+            }))
+        );
+    }
+
+    #[test]
+    fn stack_count_comes_from_footer() {
+        let input = "[A]\n 1   2   3   4 \n\nmove 1 from 1 to 4";
+        let (stacks, _) = Day05::parse(input).unwrap();
+        assert_eq!(stacks, [vec!["A"], vec![], vec![], vec![]]);
+
+        let input = " 1   2 \n\nmove 0 from 1 to 2";
+        assert_eq!(Day05::parse(input).unwrap().0, vec![Vec::<&str>::new(); 2]);
+    }
+
+    #[test]
+    fn many_stacks_and_wide_labels() {
+        let input = "\
+[ab]                                                   [kk]
+[ A] [bb] [cc] [dd] [ee] [ff] [gg] [hh] [ii] [jj] [X ] [ll]
+ 1    2    3    4    5    6    7    8    9    10   11   12
+
+move 2 from 1 to 11";
+        let (stacks, _) = Day05::parse(input).unwrap();
+        assert_eq!(stacks.len(), 12);
+        assert_eq!(stacks[0], ["A", "ab"]);
+        assert_eq!(stacks[10], ["X"]);
+        assert_eq!(stacks[11], ["ll", "kk"]);
+        assert_eq!(part1(input).unwrap(), "bbccddeeffgghhiijjAkk");
+    }
+
+    #[test]
+    fn labels_are_measured_in_characters() {
+        let input = "[é] [b]\n 1   2 \n\nmove 1 from 1 to 2";
+        let (stacks, _) = Day05::parse(input).unwrap();
+        assert_eq!(stacks, [vec!["é"], vec!["b"]]);
+        assert_eq!(part1(input).unwrap(), "é");
+
+        let input = "[éé] [ß ]\n 1    2  \n\nmove 1 from 1 to 2";
+        let (stacks, _) = Day05::parse(input).unwrap();
+        assert_eq!(stacks, [vec!["éé"], vec!["ß"]]);
+
+        let input = "[é] [b] [c]\n 1   2 \n\nmove 1 from 1 to 2";
+        assert_eq!(
+            Day05::parse(input),
+            Err(Error::Syntax(SyntaxError {
+                position: Position { line: 1, column: 9 },
+                token: "[c]".to_string(),
+            }))
+        );
+    }
+
+    #[test]
+    fn drawing_must_match_footer() {
+        let input = EXAMPLE.replace(" 1   2   3 ", " 1   3   2 ");
+        assert_eq!(
+            Day05::parse(&input),
+            Err(Error::Syntax(SyntaxError {
+                position: Position { line: 4, column: 2 },
+                token: "1   3   2 ".to_string(),
+            }))
+        );
+
+        let input = EXAMPLE.replace("[Z] [M] [P]", "[Z] [M] [P] [Q]");
+        assert_eq!(
+            Day05::parse(&input),
+            Err(Error::Syntax(SyntaxError {
+                position: Position {
+                    line: 3,
+                    column: 13
+                },
+                token: "[Q]".to_string(),
+            }))
+        );
+
+        let input = EXAMPLE.replace("[N] [C]", "[N] [CC]");
+        assert_eq!(
+            Day05::parse(&input),
+            Err(Error::Syntax(SyntaxError {
+                position: Position { line: 2, column: 5 },
+                token: "[CC]    ".to_string(),
+            }))
+        );
+    }
+
+    #[test]
+    fn moves_know_their_line() {
+        let (_, moves) = Day05::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            moves[2],
+            Move {
+                amount: 2,
+                from: 2,
+                to: 1,
+                line: 8
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_moves_are_errors() {
+        let input = EXAMPLE.replace("move 2 from 2 to 1", "move 2 from 0 to 1");
+        assert_eq!(
+            part1(&input),
+            Err(Error::NoSuchStack {
+                line: 8,
+                stack: 0,
+                stacks: 3
+            })
+        );
+
+        let input = EXAMPLE.replace("move 3 from 1 to 3", "move 4 from 1 to 3");
+        assert_eq!(
+            part2(&input),
+            Err(Error::NotEnoughCrates {
+                line: 7,
+                stack: 1,
+                amount: 4,
+                height: 3
+            })
+        );
+    }
+
+    #[test]
+    fn lenient_mode_skips_invalid_moves() {
+        let input = EXAMPLE.replace("move 2 from 2 to 1", "move 2 from 2 to 4");
+        let (top, skipped) = part1_lenient(&input).unwrap();
+        assert_eq!(top, "CZ");
+        assert_eq!(
+            skipped,
+            [
+                Skipped(Error::NoSuchStack {
+                    line: 8,
+                    stack: 4,
+                    stacks: 3
+                }),
+                Skipped(Error::EmptyStack { line: 9, stack: 1 }),
+            ]
+        );
+        assert_eq!(
+            skipped[1].to_string(),
+            "skipped: move on line 9 takes crates from empty stack 1"
+        );
+
+        assert_eq!(part2_lenient(EXAMPLE), Ok(("MCD".to_string(), vec![])));
+    }
 }
@@ -0,0 +1,151 @@
+use proptest::prelude::*;
+
+use aoc_core::Solution;
+
+use crate::crane::{BatchCrane, CrateMover9000, CrateMover9001};
+use crate::generate::Layout;
+use crate::render::render;
+use crate::reverse::unwind;
+use crate::yard::Yard;
+use crate::{generate, part1, part2, rearrange, Day05};
+
+/// Runs the rearrangement, lifting `batch(amount)` crates at a time.
+fn simulate(input: &str, batch: impl Fn(usize) -> usize) -> String {
+    let (drawing, moves) = input.split_once("\n\n").unwrap();
+    let mut rows = drawing.lines().collect::<Vec<_>>();
+    let footer = rows.pop().unwrap();
+
+    let count = footer.split_whitespace().count();
+    let cell = (footer.len() + 1) / count;
+
+    let mut stacks = vec![Vec::new(); count];
+    for row in rows.iter().rev() {
+        for (idx, stack) in stacks.iter_mut().enumerate() {
+            match row.get(idx * cell + 1..(idx + 1) * cell - 2) {
+                Some(label) if !label.trim().is_empty() => stack.push(label),
+                _ => (),
+            }
+        }
+    }
+
+    for line in moves.lines() {
+        let words = line.split(' ').collect::<Vec<_>>();
+        let amount = words[1].parse::<usize>().unwrap();
+        let from = words[3].parse::<usize>().unwrap() - 1;
+        let to = words[5].parse::<usize>().unwrap() - 1;
+
+        let mut left = amount;
+        while left > 0 {
+            let lift = batch(amount).min(left);
+            let height = stacks[from].len();
+            let crates = stacks[from].split_off(height - lift);
+            stacks[to].extend(crates);
+            left -= lift;
+        }
+    }
+
+    stacks
+        .iter()
+        .filter_map(|stack| stack.last().copied())
+        .collect()
+}
+
+proptest! {
+    #[test]
+    fn part1_matches_reference(seed in any::<u64>(), moves in 1..300usize) {
+        let input = generate::input(seed, moves);
+        prop_assert_eq!(part1(&input).unwrap(), simulate(&input, |_| 1));
+    }
+
+    #[test]
+    fn part2_matches_reference(seed in any::<u64>(), moves in 1..300usize) {
+        let input = generate::input(seed, moves);
+        prop_assert_eq!(part2(&input).unwrap(), simulate(&input, |amount| amount));
+    }
+
+    #[test]
+    fn batch_crane_matches_reference(
+        seed in any::<u64>(),
+        moves in 1..300usize,
+        capacity in 1..10usize,
+    ) {
+        let input = generate::input(seed, moves);
+        let (stacks, moves) = Day05::parse(&input).unwrap();
+        let crane = BatchCrane::new(capacity).unwrap();
+        prop_assert_eq!(
+            rearrange(&stacks, &moves, crane).unwrap(),
+            simulate(&input, |amount| amount.min(capacity))
+        );
+    }
+
+    #[test]
+    fn render_round_trips(seed in any::<u64>(), moves in 1..50usize) {
+        let input = generate::input(seed, moves);
+        let (stacks, moves) = Day05::parse(&input).unwrap();
+
+        let moves_text = moves.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let rendered = format!("{}\n\n{}", render(&stacks), moves_text.join("\n"));
+        prop_assert_eq!(&rendered, &input);
+        prop_assert_eq!(Day05::parse(&rendered).unwrap(), (stacks, moves));
+    }
+
+    #[test]
+    fn replayed_states_parse_back(seed in any::<u64>(), moves in 1..50usize) {
+        let input = generate::input(seed, moves);
+        let (stacks, moves) = Day05::parse(&input).unwrap();
+
+        for yard in Yard::new(stacks, CrateMover9001).replay(&moves) {
+            let yard = yard.unwrap();
+            if yard.stacks().iter().all(Vec::is_empty) {
+                continue;
+            }
+            let drawing = format!("{yard}\n\nmove 0 from 1 to 1");
+            let (parsed, _) = Day05::parse(&drawing).unwrap();
+            prop_assert_eq!(&parsed, yard.stacks());
+        }
+    }
+
+    #[test]
+    fn large_yards_match_reference(
+        seed in any::<u64>(),
+        stacks in 2..30usize,
+        height in 1..20usize,
+        label in 1..4usize,
+        moves in 1..100usize,
+    ) {
+        let layout = Layout { stacks, height, label };
+        let input = generate::yard(seed, layout, moves);
+        prop_assert_eq!(part1(&input).unwrap(), simulate(&input, |_| 1));
+        prop_assert_eq!(part2(&input).unwrap(), simulate(&input, |amount| amount));
+
+        let (stacks, moves) = Day05::parse(&input).unwrap();
+        let moves_text = moves.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let rendered = format!("{}\n\n{}", render(&stacks), moves_text.join("\n"));
+        prop_assert_eq!(rendered, input);
+    }
+
+    #[test]
+    fn unwinding_recovers_initial_stacks(
+        seed in any::<u64>(),
+        moves in 1..100usize,
+        capacity in 1..10usize,
+    ) {
+        let input = generate::input(seed, moves);
+        let (initial, moves) = Day05::parse(&input).unwrap();
+
+        let end = |crane| {
+            let mut yard = Yard::new(initial.clone(), crane);
+            moves.iter().for_each(|&step| yard.apply(step).unwrap());
+            yard.into_stacks()
+        };
+
+        let end_9000 = end(BatchCrane::new(1).unwrap());
+        prop_assert_eq!(unwind(&end_9000, &moves, CrateMover9000).unwrap(), initial.clone());
+
+        let end_9001 = end(BatchCrane::new(usize::MAX).unwrap());
+        prop_assert_eq!(unwind(&end_9001, &moves, CrateMover9001).unwrap(), initial.clone());
+
+        let crane = BatchCrane::new(capacity).unwrap();
+        prop_assert_eq!(unwind(&end(crane), &moves, crane).unwrap(), initial);
+    }
+}
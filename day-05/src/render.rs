@@ -0,0 +1,76 @@
+//! Drawing of crate stacks in the puzzle's own format.
+
+use crate::Stacks;
+
+/// Draws `stacks` as rows of `[A]` cells above a footer numbering the stacks.
+///
+/// Every row is padded to the full width, as in the puzzle input, and cells are wide enough
+/// for the longest label, counted in characters.
+pub fn render(stacks: &Stacks) -> String {
+    let label = stacks
+        .iter()
+        .flatten()
+        .map(|label| label.chars().count())
+        .max();
+    let width = label.unwrap_or(1) + 2;
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+
+    let mut lines = (0..height)
+        .rev()
+        .map(|row| {
+            stacks
+                .iter()
+                .map(|stack| match stack.get(row) {
+                    Some(label) => format!("[{label:^0$}]", width - 2),
+                    None => " ".repeat(width),
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>();
+
+    lines.push(
+        (1..=stacks.len())
+            .map(|id| format!("{id:^width$}"))
+            .collect::<Vec<_>>()
+            .join(" "),
+    );
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Day05, EXAMPLE};
+    use aoc_core::Solution;
+
+    #[test]
+    fn renders_example_drawing() {
+        let (stacks, _) = Day05::parse(EXAMPLE).unwrap();
+        let drawing = EXAMPLE.split_once("\n\n").unwrap().0;
+
+        let expected = drawing
+            .lines()
+            .map(|line| format!("{line:11}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(render(&stacks), expected);
+    }
+
+    #[test]
+    fn renders_empty_stacks() {
+        assert_eq!(render(&vec![vec![], vec!["A"]]), "    [A]\n 1   2 ");
+        assert_eq!(render(&vec![vec![]]), " 1 ");
+    }
+
+    #[test]
+    fn renders_non_ascii_labels() {
+        let stacks = vec![vec!["éé"], vec!["ß"]];
+        let drawing = render(&stacks);
+        assert_eq!(drawing, "[éé] [ß ]\n 1    2  ");
+
+        let input = format!("{drawing}\n\nmove 0 from 1 to 2");
+        assert_eq!(Day05::parse(&input).unwrap().0, stacks);
+    }
+}
@@ -0,0 +1,79 @@
+//! Working back from the end of a rearrangement to where it started.
+
+use crate::crane::Crane;
+use crate::yard::Yard;
+use crate::{rearrange, Error, Move, Stacks};
+
+/// Stacks from which `crane` carrying out `moves` ends up with `stacks`.
+///
+/// Fails on the last move, i.e. the first one undone, that could not have been carried out.
+pub fn unwind<'a>(
+    stacks: &Stacks<'a>,
+    moves: &[Move],
+    crane: impl Crane,
+) -> Result<Stacks<'a>, Error> {
+    let mut yard = Yard::new(stacks.clone(), crane);
+    for &step in moves.iter().rev() {
+        yard.undo(step)?;
+    }
+
+    Ok(yard.into_stacks())
+}
+
+/// Whether `crane` carrying out `moves` on `initial` leaves `message` on top of the stacks.
+pub fn produces(
+    initial: &Stacks,
+    moves: &[Move],
+    crane: impl Crane,
+    message: &str,
+) -> Result<bool, Error> {
+    rearrange(initial, moves, crane).map(|top| top == message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crane::{CrateMover9000, CrateMover9001};
+    use crate::{Day05, EXAMPLE};
+    use aoc_core::Solution;
+
+    #[test]
+    fn unwinds_example_for_both_cranes() {
+        let (initial, moves) = Day05::parse(EXAMPLE).unwrap();
+
+        let end = vec![vec!["C"], vec!["M"], vec!["P", "D", "N", "Z"]];
+        assert_eq!(unwind(&end, &moves, CrateMover9000), Ok(initial.clone()));
+
+        let end = vec![vec!["M"], vec!["C"], vec!["P", "Z", "N", "D"]];
+        assert_eq!(unwind(&end, &moves, CrateMover9001), Ok(initial));
+    }
+
+    #[test]
+    fn impossible_endings_are_errors() {
+        let (_, moves) = Day05::parse(EXAMPLE).unwrap();
+        let end = vec![vec!["C"], vec![], vec!["P", "D", "N", "Z", "M"]];
+        assert_eq!(
+            unwind(&end, &moves, CrateMover9000),
+            Err(Error::EmptyStack { line: 9, stack: 2 })
+        );
+    }
+
+    #[test]
+    fn checks_proposed_initial_state() {
+        let (initial, moves) = Day05::parse(EXAMPLE).unwrap();
+        assert_eq!(produces(&initial, &moves, CrateMover9000, "CMZ"), Ok(true));
+        assert_eq!(produces(&initial, &moves, CrateMover9001, "CMZ"), Ok(false));
+
+        let mut relabelled = initial.clone();
+        relabelled[0][0] = "Q";
+        assert_eq!(
+            produces(&relabelled, &moves, CrateMover9000, "CMZ"),
+            Ok(false)
+        );
+        assert_eq!(
+            produces(&relabelled, &moves, CrateMover9000, "CMQ"),
+            Ok(true)
+        );
+        assert!(produces(&vec![vec![]; 3], &moves, CrateMover9000, "").is_err());
+    }
+}
@@ -0,0 +1,309 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::{iter, mem, slice};
+
+use crate::crane::Crane;
+use crate::render::render;
+use crate::{Error, Move, Stacks};
+
+/// Crate stacks worked on by a single crane.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Yard<'a, C> {
+    stacks: Stacks<'a>,
+    crane: C,
+}
+
+impl<'a, C: Crane> Yard<'a, C> {
+    pub fn new(stacks: Stacks<'a>, crane: C) -> Self {
+        Yard { stacks, crane }
+    }
+
+    /// Crate labels per stack, bottom first.
+    pub fn stacks(&self) -> &Stacks<'a> {
+        &self.stacks
+    }
+
+    pub fn crane(&self) -> &C {
+        &self.crane
+    }
+
+    /// Stacks of the yard, bottom first.
+    pub fn into_stacks(self) -> Stacks<'a> {
+        self.stacks
+    }
+
+    /// Carries out one rearrangement step, lift by lift.
+    ///
+    /// Invalid steps leave the stacks untouched.
+    pub fn apply(&mut self, step: Move) -> Result<(), Error> {
+        let from = self.index(step.from, step.line)?;
+        let to = self.index(step.to, step.line)?;
+        self.check_height(from, step.from, step)?;
+        self.shift(from, to, step.amount, false);
+
+        Ok(())
+    }
+
+    /// Takes back one rearrangement step, so that applying it again restores the current
+    /// stacks.
+    ///
+    /// The crane's lifts are undone last to first, taking crates back from the destination
+    /// stack. Invalid steps leave the stacks untouched.
+    pub fn undo(&mut self, step: Move) -> Result<(), Error> {
+        let from = self.index(step.from, step.line)?;
+        let to = self.index(step.to, step.line)?;
+        self.check_height(to, step.to, step)?;
+        self.shift(to, from, step.amount, true);
+
+        Ok(())
+    }
+
+    /// Moves the top `amount` crates of stack `from` onto stack `to` in a single pass.
+    ///
+    /// Taking the crates in reverse order moves them as if one at a time, so the crates of
+    /// each lift are then put back in order. Lifts are laid out from the bottom of the moved
+    /// crates when carrying out a step, and from the top when `undo`ing one.
+    fn shift(&mut self, from: usize, to: usize, amount: u32, undo: bool) {
+        // Lifting crates off a stack and setting them back down leaves it as it was.
+        if from == to {
+            return;
+        }
+
+        let mut source = mem::take(&mut self.stacks[from]);
+        let target = &mut self.stacks[to];
+        let start = target.len();
+        target.extend(source.drain(source.len() - amount as usize..).rev());
+
+        let mut end = target.len();
+        let mut at = start;
+        for lift in lifts(&self.crane, amount) {
+            if undo {
+                target[end - lift..end].reverse();
+                end -= lift;
+            } else {
+                target[at..at + lift].reverse();
+                at += lift;
+            }
+        }
+
+        self.stacks[from] = source;
+    }
+
+    /// Checks that stack `idx`, numbered `stack`, holds the crates `step` takes from it.
+    fn check_height(&self, idx: usize, stack: u32, step: Move) -> Result<(), Error> {
+        let height = self.stacks[idx].len();
+        if step.amount > 0 && height == 0 {
+            return Err(Error::EmptyStack {
+                line: step.line,
+                stack,
+            });
+        }
+        if step.amount as usize > height {
+            return Err(Error::NotEnoughCrates {
+                line: step.line,
+                stack,
+                amount: step.amount,
+                height,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Index into `stacks` of the stack numbered `stack` in a move on line `line`.
+    fn index(&self, stack: u32, line: usize) -> Result<usize, Error> {
+        let number = stack as usize;
+        if (1..=self.stacks.len()).contains(&number) {
+            Ok(number - 1)
+        } else {
+            Err(Error::NoSuchStack {
+                line,
+                stack,
+                stacks: self.stacks.len(),
+            })
+        }
+    }
+
+    /// Label of the top crate of every non-empty stack.
+    pub fn top(&self) -> String {
+        self.stacks
+            .iter()
+            .filter_map(|stack| stack.last().copied())
+            .collect()
+    }
+}
+
+/// Sizes of the lifts `crane` makes to move `amount` crates, first to last.
+fn lifts(crane: &impl Crane, amount: u32) -> impl Iterator<Item = usize> + '_ {
+    let mut remaining = amount as usize;
+    iter::from_fn(move || {
+        (remaining > 0).then(|| {
+            let lift = crane.lift(remaining).clamp(1, remaining);
+            remaining -= lift;
+            lift
+        })
+    })
+}
+
+impl<'a, C: Crane + Clone> Yard<'a, C> {
+    /// The yard after each of `moves` in turn, ending after the first invalid one.
+    pub fn replay<'m>(self, moves: &'m [Move]) -> Replay<'m, 'a, C> {
+        Replay {
+            yard: self,
+            moves: moves.iter(),
+        }
+    }
+}
+
+/// Drawn in the format of the puzzle input.
+impl<C> Display for Yard<'_, C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&render(&self.stacks))
+    }
+}
+
+/// Iterator over the states of a yard as moves are applied, see [`Yard::replay`].
+#[derive(Debug, Clone)]
+pub struct Replay<'m, 'a, C> {
+    yard: Yard<'a, C>,
+    moves: slice::Iter<'m, Move>,
+}
+
+impl<'a, C: Crane + Clone> Iterator for Replay<'_, 'a, C> {
+    type Item = Result<Yard<'a, C>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let step = *self.moves.next()?;
+        match self.yard.apply(step) {
+            Ok(()) => Some(Ok(self.yard.clone())),
+            Err(e) => {
+                self.moves = [].iter();
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crane::{BatchCrane, CrateMover9000, CrateMover9001};
+    use crate::{Day05, EXAMPLE};
+    use aoc_core::Solution;
+
+    fn stacks() -> Stacks<'static> {
+        vec![vec!["A", "B", "C", "D"], vec![]]
+    }
+
+    fn step(amount: u32, from: u32, to: u32) -> Move {
+        Move {
+            amount,
+            from,
+            to,
+            line: 1,
+        }
+    }
+
+    #[test]
+    fn cranes_order_lifted_crates() {
+        let mut yard = Yard::new(stacks(), CrateMover9000);
+        yard.apply(step(3, 1, 2)).unwrap();
+        assert_eq!(yard.stacks(), &[vec!["A"], vec!["D", "C", "B"]]);
+
+        let mut yard = Yard::new(stacks(), CrateMover9001);
+        yard.apply(step(3, 1, 2)).unwrap();
+        assert_eq!(yard.stacks(), &[vec!["A"], vec!["B", "C", "D"]]);
+
+        let mut yard = Yard::new(stacks(), BatchCrane::new(2).unwrap());
+        yard.apply(step(3, 1, 2)).unwrap();
+        assert_eq!(yard.stacks(), &[vec!["A"], vec!["C", "D", "B"]]);
+        assert_eq!(yard.top(), "AB");
+    }
+
+    #[test]
+    fn moving_onto_the_same_stack_keeps_it() {
+        let mut yard = Yard::new(stacks(), CrateMover9000);
+        yard.apply(step(2, 1, 1)).unwrap();
+        assert_eq!(yard.stacks(), &stacks());
+    }
+
+    #[test]
+    fn undo_takes_back_each_crane_step() {
+        let mut yard = Yard::new(stacks(), BatchCrane::new(3).unwrap());
+        for step in [step(4, 1, 2), step(2, 2, 1)] {
+            yard.apply(step).unwrap();
+        }
+        assert_eq!(yard.stacks(), &[vec!["D", "A"], vec!["B", "C"]]);
+
+        yard.undo(step(2, 2, 1)).unwrap();
+        assert_eq!(yard.stacks(), &[vec![], vec!["B", "C", "D", "A"]]);
+        yard.undo(step(4, 1, 2)).unwrap();
+        assert_eq!(yard.stacks(), &stacks());
+
+        assert_eq!(
+            yard.undo(step(1, 1, 2)),
+            Err(Error::EmptyStack { line: 1, stack: 2 })
+        );
+    }
+
+    #[test]
+    fn invalid_steps_leave_stacks_untouched() {
+        let mut yard = Yard::new(stacks(), CrateMover9001);
+        assert_eq!(
+            yard.apply(step(1, 3, 1)),
+            Err(Error::NoSuchStack {
+                line: 1,
+                stack: 3,
+                stacks: 2
+            })
+        );
+        assert_eq!(
+            yard.apply(step(1, 2, 1)),
+            Err(Error::EmptyStack { line: 1, stack: 2 })
+        );
+        assert_eq!(
+            yard.apply(step(5, 1, 2)),
+            Err(Error::NotEnoughCrates {
+                line: 1,
+                stack: 1,
+                amount: 5,
+                height: 4
+            })
+        );
+        assert_eq!(yard.stacks(), &stacks());
+
+        yard.apply(step(0, 2, 1)).unwrap();
+        assert_eq!(yard.stacks(), &stacks());
+    }
+
+    #[test]
+    fn replay_yields_each_state() {
+        let (stacks, moves) = Day05::parse(EXAMPLE).unwrap();
+        let tops = Yard::new(stacks, CrateMover9000)
+            .replay(&moves)
+            .map(|yard| yard.unwrap().top())
+            .collect::<Vec<_>>();
+        assert_eq!(tops, ["DCP", "CZ", "MZ", "CMZ"]);
+    }
+
+    #[test]
+    fn replay_stops_at_invalid_move() {
+        let moves = [step(4, 1, 2), step(1, 1, 2)];
+        let mut replay = Yard::new(stacks(), CrateMover9000).replay(&moves);
+        assert!(replay.next().unwrap().is_ok());
+        assert_eq!(
+            replay.next(),
+            Some(Err(Error::EmptyStack { line: 1, stack: 1 }))
+        );
+        assert_eq!(replay.next(), None);
+    }
+
+    #[test]
+    fn displays_drawing() {
+        let yard = Yard::new(stacks(), CrateMover9001);
+        assert_eq!(
+            yard.to_string(),
+            "[D]    \n[C]    \n[B]    \n[A]    \n 1   2 "
+        );
+    }
+}
@@ -0,0 +1,40 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+/// Datastream of `len` lowercase letters whose start-of-message marker is at the very end.
+///
+/// `len` is raised to 14 if smaller, so that the marker fits.
+pub fn input(seed: u64, len: usize) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut letters = ('a'..='z').collect::<Vec<_>>();
+    letters.shuffle(&mut rng);
+
+    // Thirteen letters can never form a start-of-message marker, but plenty of packet markers.
+    let noise = &letters[..13];
+    let mut datastream = (0..len.saturating_sub(14))
+        .map(|_| *noise.choose(&mut rng).unwrap())
+        .collect::<String>();
+
+    // Reusing the noise letters keeps every earlier window short of fourteen distinct ones.
+    let mut tail = noise.to_vec();
+    tail.shuffle(&mut rng);
+    datastream.extend(tail);
+    datastream.push(letters[13]);
+
+    datastream
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn generated_input_is_valid() {
+        let generated = input(6, 500);
+        assert_eq!(generated, input(6, 500));
+        assert!(part1(&generated).is_ok());
+        assert_eq!(part2(&generated), Ok("500".to_string()));
+    }
+}
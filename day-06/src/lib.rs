@@ -1,40 +1,82 @@
+pub mod generate;
+#[cfg(test)]
+mod reference;
+
 use std::collections::HashSet;
 
-pub fn part1(input: &str) -> String {
-    unique_sequence_end_idx(input, 4)
+use aoc_core::{Part, Solution};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum Error {
+    #[error("no run of {len} distinct characters in the datastream")]
+    NoMarker { len: usize },
+}
+
+/// Example from the puzzle description.
+pub const EXAMPLE: &str = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Tuning Trouble";
+
+    type Input<'a> = Vec<char>;
+    type Output1 = usize;
+    type Output2 = usize;
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        Ok(input.chars().collect())
+    }
+
+    fn part1(datastream: &Self::Input<'_>) -> Result<Self::Output1, Self::Error> {
+        unique_sequence_end_idx(datastream, 4)
+    }
+
+    fn part2(datastream: &Self::Input<'_>) -> Result<Self::Output2, Self::Error> {
+        unique_sequence_end_idx(datastream, 14)
+    }
+}
+
+pub fn part1(input: &str) -> Result<String, Error> {
+    Day06::solve(Part::One, input)
 }
 
-pub fn part2(input: &str) -> String {
-    unique_sequence_end_idx(input, 14)
+pub fn part2(input: &str) -> Result<String, Error> {
+    Day06::solve(Part::Two, input)
 }
 
-fn unique_sequence_end_idx(input: &str, len: usize) -> String {
-    let (idx, _) = input
-        .chars()
-        .collect::<Vec<_>>()
+fn unique_sequence_end_idx(datastream: &[char], len: usize) -> Result<usize, Error> {
+    let (idx, _) = datastream
         .windows(len)
         .enumerate()
         .find(|(_, window)| window.iter().collect::<HashSet<_>>().len() == len)
-        .unwrap();
+        .ok_or(Error::NoMarker { len })?;
 
-    (idx + len).to_string()
+    Ok(idx + len)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &str = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
-
     #[test]
     fn part1_works() {
-        let result = part1(INPUT);
+        let result = part1(EXAMPLE).unwrap();
         assert_eq!(result, "7");
     }
 
     #[test]
     fn part2_works() {
-        let result = part2(INPUT);
+        let result = part2(EXAMPLE).unwrap();
         assert_eq!(result, "19");
     }
+
+    #[test]
+    fn missing_marker_is_reported() {
+        let result = part1("abcabcabc");
+        assert_eq!(result, Err(Error::NoMarker { len: 4 }));
+    }
 }
@@ -0,0 +1,29 @@
+use proptest::prelude::*;
+
+use crate::{generate, part1, part2};
+
+/// First position after `len` pairwise distinct characters, comparing every pair.
+fn marker(input: &str, len: usize) -> usize {
+    let chars = input.chars().collect::<Vec<_>>();
+
+    (len..=chars.len())
+        .find(|&end| {
+            let window = &chars[end - len..end];
+            (0..len).all(|i| (i + 1..len).all(|j| window[i] != window[j]))
+        })
+        .unwrap()
+}
+
+proptest! {
+    #[test]
+    fn part1_matches_reference(seed in any::<u64>(), len in 14..2000usize) {
+        let input = generate::input(seed, len);
+        prop_assert_eq!(part1(&input).unwrap(), marker(&input, 4).to_string());
+    }
+
+    #[test]
+    fn part2_matches_reference(seed in any::<u64>(), len in 14..2000usize) {
+        let input = generate::input(seed, len);
+        prop_assert_eq!(part2(&input).unwrap(), marker(&input, 14).to_string());
+    }
+}
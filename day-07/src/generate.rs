@@ -0,0 +1,83 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+/// Shell transcript exploring a random tree of `directories` directories below `/`.
+///
+/// File sizes are scaled down as the tree grows so the whole disk stays under 50 MB.
+pub fn input(seed: u64, directories: usize) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut children: Vec<Vec<usize>> = vec![vec![]];
+    for id in 1..=directories {
+        let parent = rng.gen_range(0..id);
+        children[parent].push(id);
+        children.push(vec![]);
+    }
+
+    let max_file = (50_000_000 / (4 * (directories + 1))).clamp(1, 300_000) as u32;
+
+    let mut lines = vec!["$ cd /".to_string()];
+    explore(&mut rng, &children, 0, max_file, &mut lines);
+
+    lines.join("\n")
+}
+
+fn explore(
+    rng: &mut StdRng,
+    children: &[Vec<usize>],
+    dir: usize,
+    max_file: u32,
+    lines: &mut Vec<String>,
+) {
+    let subdirs = &children[dir];
+
+    lines.push("$ ls".to_string());
+    for idx in 0..subdirs.len() {
+        lines.push(format!("dir {}", name(idx)));
+    }
+
+    // `ls` must list something, so leaf directories always hold a file.
+    let files = rng.gen_range(usize::from(subdirs.is_empty())..=4);
+    for idx in 0..files {
+        let extension = ["txt", "dat", "log", "lst"].choose(rng).unwrap();
+        let size = rng.gen_range(1..=max_file);
+        lines.push(format!("{size} {}.{extension}", name(idx)));
+    }
+
+    for (idx, &subdir) in subdirs.iter().enumerate() {
+        lines.push(format!("$ cd {}", name(idx)));
+        explore(rng, children, subdir, max_file, lines);
+        lines.push("$ cd ..".to_string());
+    }
+}
+
+/// Spreadsheet-style letter name: `a`, `b`, ..., `z`, `aa`, `ab`, ...
+fn name(mut idx: usize) -> String {
+    let mut name = Vec::new();
+
+    loop {
+        name.push(b'a' + (idx % 26) as u8);
+        if idx < 26 {
+            break;
+        }
+        idx = idx / 26 - 1;
+    }
+    name.reverse();
+
+    String::from_utf8(name).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn generated_input_is_valid() {
+        let generated = input(7, 200);
+        assert_eq!(generated, input(7, 200));
+        assert!(part1(&generated).is_ok());
+        assert!(part2(&generated).is_ok());
+    }
+}
@@ -1,41 +1,60 @@
+pub mod generate;
+#[cfg(test)]
+mod reference;
+
 use std::collections::BTreeMap;
 use std::ops::Add;
 
 use nom::branch::alt;
 use nom::bytes::complete::{is_not, tag};
-use nom::character::complete::{alpha1, digit1, newline};
+use nom::character::complete;
+use nom::character::complete::{alpha1, newline};
 use nom::multi::separated_list1;
 use nom::IResult;
+use thiserror::Error;
+use tracing::debug;
+
+use aoc_core::{parse_all, Part, Solution, SyntaxError};
 
 use crate::Files::{Dir, File};
 use crate::Move::{Down, Root, Up};
 
+#[derive(Debug, Error, PartialEq)]
+pub enum Error {
+    #[error(transparent)]
+    Syntax(#[from] SyntaxError),
+    #[error("transcript never lists the root directory")]
+    NoRoot,
+    #[error("{used} bytes used exceeds the disk size")]
+    DiskOverflow { used: u32 },
+}
+
 #[derive(Debug)]
-enum Action<'a> {
+pub enum Action<'a> {
     Ls(Vec<Files<'a>>),
     Cd(Move<'a>),
 }
 
 #[derive(Debug)]
-enum Move<'a> {
+pub enum Move<'a> {
     Root,
     Up,
     Down(&'a str),
 }
 
 #[derive(Debug)]
-enum Files<'a> {
+pub enum Files<'a> {
     File { name: &'a str, size: u32 },
     Dir(&'a str),
 }
 
-fn action(input: &str) -> IResult<&str, Vec<Action>> {
+fn action(input: &str) -> IResult<&str, Vec<Action<'_>>> {
     let (input, actions) = separated_list1(newline, alt((ls, cd)))(input)?;
 
     Ok((input, actions))
 }
 
-fn ls(input: &str) -> IResult<&str, Action> {
+fn ls(input: &str) -> IResult<&str, Action<'_>> {
     let (input, _) = tag("$ ls")(input)?;
     let (input, _) = newline(input)?;
     let (input, files) = files(input)?;
@@ -43,7 +62,7 @@ fn ls(input: &str) -> IResult<&str, Action> {
     Ok((input, Action::Ls(files)))
 }
 
-fn cd(input: &str) -> IResult<&str, Action> {
+fn cd(input: &str) -> IResult<&str, Action<'_>> {
     let (input, _) = tag("$ cd ")(input)?;
     let (input, directory) = alt((tag(".."), alpha1, tag("/")))(input)?;
 
@@ -56,40 +75,34 @@ fn cd(input: &str) -> IResult<&str, Action> {
     Ok((input, op))
 }
 
-fn files(input: &str) -> IResult<&str, Vec<Files>> {
+fn files(input: &str) -> IResult<&str, Vec<Files<'_>>> {
     let (input, x) = separated_list1(newline, alt((dir, file)))(input)?;
     Ok((input, x))
 }
 
-fn dir(input: &str) -> IResult<&str, Files> {
+fn dir(input: &str) -> IResult<&str, Files<'_>> {
     let (input, _) = tag("dir ")(input)?;
     let (input, name) = alpha1(input)?;
 
     Ok((input, Dir(name)))
 }
 
-fn file(input: &str) -> IResult<&str, Files> {
-    let (input, size) = digit1(input)?;
+fn file(input: &str) -> IResult<&str, Files<'_>> {
+    let (input, size) = complete::u32(input)?;
     let (input, _) = tag(" ")(input)?;
     let (input, name) = is_not("\n")(input)?;
 
-    Ok((
-        input,
-        File {
-            size: size.parse::<u32>().unwrap(),
-            name,
-        },
-    ))
+    Ok((input, File { size, name }))
 }
 
+#[allow(dead_code)]
 #[derive(Debug)]
 struct FileStruct<'a> {
     size: u32,
     name: &'a str,
 }
-pub fn part1(input: &str) -> String {
-    let cmds = action(input).unwrap().1;
 
+fn directory_sizes(cmds: &[Action]) -> BTreeMap<String, u32> {
     let mut dirs: BTreeMap<String, Vec<FileStruct>> = BTreeMap::new();
     let mut context: Vec<&str> = Vec::new();
 
@@ -101,7 +114,12 @@ pub fn part1(input: &str) -> String {
                     .map(|s| s.to_string().add("/"))
                     .collect::<String>();
                 key.pop();
-                dirs.entry(key.clone()).or_insert(Vec::new());
+                debug!(
+                    dir = if key.is_empty() { "/" } else { &key },
+                    entries = files.len(),
+                    "ls"
+                );
+                dirs.entry(key.clone()).or_default();
 
                 for file in files.iter() {
                     match file {
@@ -113,75 +131,16 @@ pub fn part1(input: &str) -> String {
                     }
                 }
             }
-            Action::Cd(Root) => context.push(""),
-            Action::Cd(Up) => {
-                context.pop();
-            }
-            Action::Cd(Down(name)) => {
-                context.push(name);
+            Action::Cd(Root) => {
+                debug!(to = "/", "cd");
+                context.push("");
             }
-        }
-    }
-
-    let mut sizes: BTreeMap<String, u32> = BTreeMap::new();
-
-    for (path, files) in dirs.iter() {
-        let directories = path.split("/").collect::<Vec<_>>();
-        let size = files
-            .iter()
-            .map(|FileStruct { size, .. }| size)
-            .sum::<u32>();
-
-        for x in 0..directories.len() {
-            let mut key = directories[..=x]
-                .iter()
-                .map(|s| s.to_string().add("/"))
-                .collect::<String>();
-            key.pop();
-
-            sizes.entry(key).and_modify(|v| *v += size).or_insert(size);
-        }
-    }
-
-    sizes
-        .iter()
-        .filter(|(_, &size)| size < 100000)
-        .map(|(_, size)| size)
-        .sum::<u32>()
-        .to_string()
-}
-
-pub fn part2(input: &str) -> String {
-    let cmds = action(input).unwrap().1;
-
-    let mut dirs: BTreeMap<String, Vec<FileStruct>> = BTreeMap::new();
-    let mut context: Vec<&str> = Vec::new();
-
-    for command in cmds.iter() {
-        match command {
-            Action::Ls(files) => {
-                let mut key = context
-                    .iter()
-                    .map(|s| s.to_string().add("/"))
-                    .collect::<String>();
-                key.pop();
-                dirs.entry(key.clone()).or_insert(Vec::new());
-
-                for file in files.iter() {
-                    match file {
-                        File { size, name } => {
-                            dirs.entry(key.clone())
-                                .and_modify(|fs| fs.push(FileStruct { size: *size, name }));
-                        }
-                        Dir(_) => (),
-                    }
-                }
-            }
-            Action::Cd(Root) => context.push(""),
             Action::Cd(Up) => {
+                debug!(to = "..", "cd");
                 context.pop();
             }
             Action::Cd(Down(name)) => {
+                debug!(to = name, "cd");
                 context.push(name);
             }
         }
@@ -207,27 +166,11 @@ pub fn part2(input: &str) -> String {
         }
     }
 
-    let total_size = *sizes.get("").unwrap();
-
-    let required_space = 30_000_000 as u32;
-    let total_space = 70_000_000 as u32;
-    let free_space = total_space - total_size;
-    let space_to_free = required_space - free_space;
-
     sizes
-        .iter()
-        .filter(|(_, &size)| size >= space_to_free)
-        .map(|(_, size)| size)
-        .min()
-        .unwrap()
-        .to_string()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const INPUT: &str = "$ cd /
+/// Example from the puzzle description.
+pub const EXAMPLE: &str = "$ cd /
 $ ls
 dir a
 14848514 b.txt
@@ -251,15 +194,93 @@ $ ls
 5626152 d.ext
 7214296 k";
 
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "No Space Left On Device";
+
+    type Input<'a> = Vec<Action<'a>>;
+    type Output1 = u32;
+    type Output2 = u32;
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        Ok(parse_all(input, action(input))?)
+    }
+
+    fn part1(cmds: &Self::Input<'_>) -> Result<Self::Output1, Self::Error> {
+        let result = directory_sizes(cmds)
+            .iter()
+            .filter(|(_, &size)| size < 100000)
+            .map(|(_, size)| size)
+            .sum::<u32>();
+
+        Ok(result)
+    }
+
+    fn part2(cmds: &Self::Input<'_>) -> Result<Self::Output2, Self::Error> {
+        let sizes = directory_sizes(cmds);
+
+        let total_size = *sizes.get("").ok_or(Error::NoRoot)?;
+
+        let required_space = 30_000_000_u32;
+        let total_space = 70_000_000_u32;
+        let free_space = total_space
+            .checked_sub(total_size)
+            .ok_or(Error::DiskOverflow { used: total_size })?;
+        let space_to_free = required_space.saturating_sub(free_space);
+
+        let result = sizes
+            .iter()
+            .filter(|(_, &size)| size >= space_to_free)
+            .map(|(_, size)| *size)
+            .min()
+            .unwrap_or(total_size);
+
+        Ok(result)
+    }
+}
+
+pub fn part1(input: &str) -> Result<String, Error> {
+    Day07::solve(Part::One, input)
+}
+
+pub fn part2(input: &str) -> Result<String, Error> {
+    Day07::solve(Part::Two, input)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_core::Position;
+
+    use super::*;
+
     #[test]
     fn part1_works() {
-        let result = part1(INPUT);
+        let result = part1(EXAMPLE).unwrap();
         assert_eq!(result, "95437");
     }
 
     #[test]
     fn part2_works() {
-        let result = part2(INPUT);
+        let result = part2(EXAMPLE).unwrap();
         assert_eq!(result, "24933642");
     }
+
+    #[test]
+    fn unknown_command_is_located() {
+        let input = EXAMPLE.replace("$ cd e", "$ rm -rf e");
+        let result = part1(&input);
+        assert_eq!(
+            result,
+            Err(Error::Syntax(SyntaxError {
+                position: Position {
+                    line: 13,
+                    column: 1
+                },
+                token: "$ rm -rf e".to_string(),
+            }))
+        );
+    }
 }
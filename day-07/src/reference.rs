@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+
+use proptest::prelude::*;
+
+use crate::{generate, part1, part2};
+
+/// Total size of every listed directory, adding each file to all of its ancestors.
+fn sizes(input: &str) -> HashMap<String, u32> {
+    let mut sizes = HashMap::new();
+    let mut path: Vec<&str> = Vec::new();
+
+    for line in input.lines() {
+        let words = line.split(' ').collect::<Vec<_>>();
+
+        match words[..] {
+            ["$", "cd", "/"] => path.clear(),
+            ["$", "cd", ".."] => {
+                path.pop();
+            }
+            ["$", "cd", name] => path.push(name),
+            ["$", "ls"] => {
+                sizes.entry(path.join("/")).or_insert(0);
+            }
+            ["dir", _] => (),
+            [size, _] => {
+                let size = size.parse::<u32>().unwrap();
+                for depth in 0..=path.len() {
+                    *sizes.entry(path[..depth].join("/")).or_insert(0) += size;
+                }
+            }
+            _ => unreachable!("unexpected line {line:?}"),
+        }
+    }
+
+    sizes
+}
+
+fn small_directories(input: &str) -> u32 {
+    sizes(input).values().filter(|size| **size < 100000).sum()
+}
+
+fn directory_to_delete(input: &str) -> u32 {
+    let sizes = sizes(input);
+    let needed = sizes[""].saturating_sub(40_000_000);
+
+    *sizes
+        .values()
+        .filter(|size| **size >= needed)
+        .min()
+        .unwrap()
+}
+
+proptest! {
+    #[test]
+    fn part1_matches_reference(seed in any::<u64>(), directories in 0..200usize) {
+        let input = generate::input(seed, directories);
+        prop_assert_eq!(part1(&input).unwrap(), small_directories(&input).to_string());
+    }
+
+    #[test]
+    fn part2_matches_reference(seed in any::<u64>(), directories in 0..200usize) {
+        let input = generate::input(seed, directories);
+        prop_assert_eq!(part2(&input).unwrap(), directory_to_delete(&input).to_string());
+    }
+}
@@ -0,0 +1,30 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Square tree grid `side` trees wide.
+pub fn input(seed: u64, side: usize) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    (0..side)
+        .map(|_| {
+            (0..side)
+                .map(|_| char::from(b'0' + rng.gen_range(0..=9)))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn generated_input_is_valid() {
+        let generated = input(8, 50);
+        assert_eq!(generated, input(8, 50));
+        assert!(part1(&generated).is_ok());
+        assert!(part2(&generated).is_ok());
+    }
+}
@@ -1,118 +1,192 @@
-pub fn part1(input: &str) -> String {
-    let trees_matrix = input
-        .lines()
-        .map(|line| {
-            line.split("")
-                .skip(1)
-                .take(line.len())
-                .map(|symbol| symbol.parse::<usize>().unwrap())
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
-
-    let height = trees_matrix.len();
-    let width = trees_matrix.first().unwrap().len();
-
-    let mut result = ((height + width) * 2) - 4;
-
-    for row in 1..(height - 1) {
-        for col in 1..(width - 1) {
-            let tree = trees_matrix[row][col];
-
-            if !(trees_matrix[row][..col].iter().any(|t| *t >= tree)
-                && trees_matrix[row][(col + 1)..].iter().any(|t| *t >= tree)
-                && trees_matrix[..row].iter().any(|c| c[col] >= tree)
-                && trees_matrix[(row + 1)..].iter().any(|c| c[col] >= tree))
-            {
-                result += 1;
-            }
+pub mod generate;
+#[cfg(test)]
+mod reference;
+
+use aoc_core::{Part, Position, Solution};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum Error {
+    #[error("invalid tree height {token:?} at {position}")]
+    InvalidHeight { position: Position, token: String },
+    #[error("row on line {line} has {found} trees, expected {expected}")]
+    RaggedRow {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    #[error("no trees in input")]
+    EmptyGrid,
+}
+
+/// Example from the puzzle description.
+pub const EXAMPLE: &str = "30373
+25512
+65332
+33549
+35390";
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "Treetop Tree House";
+
+    type Input<'a> = Vec<Vec<usize>>;
+    type Output1 = usize;
+    type Output2 = usize;
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        let trees_matrix = input
+            .lines()
+            .map(|line| {
+                line.split("")
+                    .skip(1)
+                    .take(line.len())
+                    .map(|symbol| {
+                        symbol.parse::<usize>().map_err(|_| Error::InvalidHeight {
+                            position: Position::of(input, symbol),
+                            token: symbol.to_string(),
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let width = trees_matrix.first().ok_or(Error::EmptyGrid)?.len();
+        if width == 0 {
+            return Err(Error::EmptyGrid);
         }
-    }
 
-    result.to_string()
-}
+        if let Some((idx, row)) = trees_matrix
+            .iter()
+            .enumerate()
+            .find(|(_, row)| row.len() != width)
+        {
+            return Err(Error::RaggedRow {
+                line: idx + 1,
+                expected: width,
+                found: row.len(),
+            });
+        }
 
-pub fn part2(input: &str) -> String {
-    let trees_matrix = input
-        .lines()
-        .map(|line| {
-            line.split("")
-                .skip(1)
-                .take(line.len())
-                .map(|symbol| symbol.parse::<usize>().unwrap())
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
-
-    let height = trees_matrix.len();
-    let width = trees_matrix.first().unwrap().len();
-
-    let mut result = Vec::<usize>::new();
-
-    for row in 1..(height - 1) {
-        for col in 1..(width - 1) {
-            let tree = trees_matrix[row][col];
-
-            let mut l_view = trees_matrix[row][..col]
-                .iter()
-                .rev()
-                .take_while(|&t| *t < tree)
-                .count();
-            if l_view < col {
-                l_view += 1;
-            }
+        Ok(trees_matrix)
+    }
 
-            let mut r_view = trees_matrix[row][(col + 1)..]
-                .iter()
-                .take_while(|&t| *t < tree)
-                .count();
-            if r_view < width - (col + 1) {
-                r_view += 1;
+    fn part1(trees_matrix: &Self::Input<'_>) -> Result<Self::Output1, Self::Error> {
+        let height = trees_matrix.len();
+        let width = trees_matrix.first().ok_or(Error::EmptyGrid)?.len();
+
+        let mut result = if height == 1 || width == 1 {
+            height * width
+        } else {
+            ((height + width) * 2) - 4
+        };
+
+        for row in 1..(height - 1) {
+            for col in 1..(width - 1) {
+                let tree = trees_matrix[row][col];
+
+                if !(trees_matrix[row][..col].iter().any(|t| *t >= tree)
+                    && trees_matrix[row][(col + 1)..].iter().any(|t| *t >= tree)
+                    && trees_matrix[..row].iter().any(|c| c[col] >= tree)
+                    && trees_matrix[(row + 1)..].iter().any(|c| c[col] >= tree))
+                {
+                    result += 1;
+                }
             }
+        }
 
-            let mut u_view = trees_matrix[..row]
-                .iter()
-                .rev()
-                .take_while(|&t| t[col] < tree)
-                .count();
-            if u_view < row {
-                u_view += 1;
-            }
+        Ok(result)
+    }
 
-            let mut d_view = trees_matrix[(row + 1)..]
-                .iter()
-                .take_while(|&t| t[col] < tree)
-                .count();
-            if d_view < height - (row + 1) {
-                d_view += 1;
+    fn part2(trees_matrix: &Self::Input<'_>) -> Result<Self::Output2, Self::Error> {
+        let height = trees_matrix.len();
+        let width = trees_matrix.first().ok_or(Error::EmptyGrid)?.len();
+
+        let mut result = Vec::<usize>::new();
+
+        for row in 1..(height - 1) {
+            for col in 1..(width - 1) {
+                let tree = trees_matrix[row][col];
+
+                let mut l_view = trees_matrix[row][..col]
+                    .iter()
+                    .rev()
+                    .take_while(|&t| *t < tree)
+                    .count();
+                if l_view < col {
+                    l_view += 1;
+                }
+
+                let mut r_view = trees_matrix[row][(col + 1)..]
+                    .iter()
+                    .take_while(|&t| *t < tree)
+                    .count();
+                if r_view < width - (col + 1) {
+                    r_view += 1;
+                }
+
+                let mut u_view = trees_matrix[..row]
+                    .iter()
+                    .rev()
+                    .take_while(|&t| t[col] < tree)
+                    .count();
+                if u_view < row {
+                    u_view += 1;
+                }
+
+                let mut d_view = trees_matrix[(row + 1)..]
+                    .iter()
+                    .take_while(|&t| t[col] < tree)
+                    .count();
+                if d_view < height - (row + 1) {
+                    d_view += 1;
+                }
+
+                result.push(l_view * r_view * u_view * d_view)
             }
-
-            result.push(l_view * r_view * u_view * d_view)
         }
+
+        Ok(result.iter().max().copied().unwrap_or(0))
     }
+}
+
+pub fn part1(input: &str) -> Result<String, Error> {
+    Day08::solve(Part::One, input)
+}
 
-    result.iter().max().unwrap().to_string()
+pub fn part2(input: &str) -> Result<String, Error> {
+    Day08::solve(Part::Two, input)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &str = "30373
-25512
-65332
-33549
-35390";
-
     #[test]
     fn part1_works() {
-        let result = part1(INPUT);
+        let result = part1(EXAMPLE).unwrap();
         assert_eq!(result, "21");
     }
 
     #[test]
     fn part2_works() {
-        let result = part2(INPUT);
+        let result = part2(EXAMPLE).unwrap();
         assert_eq!(result, "8");
     }
+
+    #[test]
+    fn ragged_row_is_reported() {
+        let result = part1("30373\n2551\n65332");
+        assert_eq!(
+            result,
+            Err(Error::RaggedRow {
+                line: 2,
+                expected: 5,
+                found: 4,
+            })
+        );
+    }
 }
@@ -0,0 +1,78 @@
+use proptest::prelude::*;
+
+use crate::{generate, part1, part2};
+
+const DIRECTIONS: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+fn grid(input: &str) -> Vec<Vec<u8>> {
+    input.lines().map(|line| line.bytes().collect()).collect()
+}
+
+/// Heights met walking from `(row, col)` towards the edge, excluding the start.
+fn walk(grid: &[Vec<u8>], row: usize, col: usize, (dr, dc): (isize, isize)) -> Vec<u8> {
+    let mut heights = Vec::new();
+    let (mut r, mut c) = (row as isize + dr, col as isize + dc);
+
+    while r >= 0 && c >= 0 && (r as usize) < grid.len() && (c as usize) < grid[0].len() {
+        heights.push(grid[r as usize][c as usize]);
+        r += dr;
+        c += dc;
+    }
+
+    heights
+}
+
+fn trees(grid: &[Vec<u8>]) -> impl Iterator<Item = (usize, usize)> + '_ {
+    (0..grid.len()).flat_map(move |row| (0..grid[0].len()).map(move |col| (row, col)))
+}
+
+fn visible(input: &str) -> usize {
+    let grid = grid(input);
+
+    trees(&grid)
+        .filter(|&(row, col)| {
+            DIRECTIONS.iter().any(|&direction| {
+                walk(&grid, row, col, direction)
+                    .iter()
+                    .all(|height| *height < grid[row][col])
+            })
+        })
+        .count()
+}
+
+fn best_scenic_score(input: &str) -> usize {
+    let grid = grid(input);
+
+    trees(&grid)
+        .map(|(row, col)| {
+            DIRECTIONS
+                .iter()
+                .map(|&direction| {
+                    let mut seen = 0;
+                    for height in walk(&grid, row, col, direction) {
+                        seen += 1;
+                        if height >= grid[row][col] {
+                            break;
+                        }
+                    }
+                    seen
+                })
+                .product::<usize>()
+        })
+        .max()
+        .unwrap()
+}
+
+proptest! {
+    #[test]
+    fn part1_matches_reference(seed in any::<u64>(), side in 1..30usize) {
+        let input = generate::input(seed, side);
+        prop_assert_eq!(part1(&input).unwrap(), visible(&input).to_string());
+    }
+
+    #[test]
+    fn part2_matches_reference(seed in any::<u64>(), side in 1..30usize) {
+        let input = generate::input(seed, side);
+        prop_assert_eq!(part2(&input).unwrap(), best_scenic_score(&input).to_string());
+    }
+}
@@ -0,0 +1,32 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+/// Series of `motions` head motions of 1 to 20 steps each.
+pub fn input(seed: u64, motions: usize) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    (0..motions)
+        .map(|_| {
+            let direction = ["U", "D", "L", "R"].choose(&mut rng).unwrap();
+            let count = rng.gen_range(1..=20);
+
+            format!("{direction} {count}")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn generated_input_is_valid() {
+        let generated = input(9, 500);
+        assert_eq!(generated, input(9, 500));
+        assert!(part1(&generated).is_ok());
+        assert!(part2(&generated).is_ok());
+    }
+}
@@ -1,12 +1,29 @@
+pub mod generate;
+#[cfg(test)]
+mod reference;
+
 use std::collections::HashSet;
 
 use ::lending_iterator::prelude::*;
 use itertools::Itertools;
 use nom::bytes::complete::tag;
-use nom::character::complete::{alpha1, digit1, newline};
+use nom::character::complete;
+use nom::character::complete::{alpha1, newline};
 use nom::multi::separated_list1;
 use nom::sequence::separated_pair;
 use nom::IResult;
+use thiserror::Error;
+use tracing::debug;
+
+use aoc_core::{parse_all, Part, Position, Solution, SyntaxError};
+
+#[derive(Debug, Error, PartialEq)]
+pub enum Error {
+    #[error(transparent)]
+    Syntax(#[from] SyntaxError),
+    #[error("invalid direction {token:?} at {position}")]
+    InvalidDirection { position: Position, token: String },
+}
 
 fn far(from: (i32, i32), to: (i32, i32)) -> bool {
     let x = from.0 - to.0;
@@ -20,182 +37,214 @@ fn diagonal(from: (i32, i32), to: (i32, i32)) -> bool {
 }
 
 fn moves(input: &str) -> IResult<&str, (&str, u32)> {
-    let (input, (mov, count)) = separated_pair(alpha1, tag(" "), digit1)(input)?;
+    separated_pair(alpha1, tag(" "), complete::u32)(input)
+}
 
-    Ok((input, (mov, count.parse::<u32>().unwrap())))
+fn direction(dir: &str) -> Option<(i32, i32)> {
+    match dir {
+        "U" => Some((1, 0)),
+        "D" => Some((-1, 0)),
+        "L" => Some((0, -1)),
+        "R" => Some((0, 1)),
+        _ => None,
+    }
 }
 
-pub fn part1(input: &str) -> String {
-    let mut head = (0, 0);
-    let mut tail = (0, 0);
-    let mut result: HashSet<(i32, i32)> = HashSet::new();
-    result.insert(tail.clone());
+/// Example from the puzzle description.
+pub const EXAMPLE: &str = "R 4
+U 4
+L 3
+D 1
+R 4
+D 1
+L 5
+R 2";
 
-    let (_, moves): (&str, Vec<(&str, u32)>) = separated_list1(newline, moves)(input).unwrap();
+/// Larger example from the second part of the puzzle description.
+pub const LARGER_EXAMPLE: &str = "R 5
+U 8
+L 8
+D 3
+R 17
+D 10
+L 25
+U 20";
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    const TITLE: &'static str = "Rope Bridge";
+
+    /// Unit step of each motion together with its repeat count.
+    type Input<'a> = Vec<((i32, i32), u32)>;
+    type Output1 = usize;
+    type Output2 = usize;
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Self::Error> {
+        let moves = parse_all(input, separated_list1(newline, moves)(input))?;
 
-    for (dir, count) in moves {
-        let step = direction(dir);
+        moves
+            .into_iter()
+            .map(|(dir, count)| {
+                let step = direction(dir).ok_or_else(|| Error::InvalidDirection {
+                    position: Position::of(input, dir),
+                    token: dir.to_string(),
+                })?;
 
-        for _ in 0..count {
-            head.0 += step.0;
-            head.1 += step.1;
+                Ok((step, count))
+            })
+            .collect()
+    }
 
-            if far(tail, head) {
-                if diagonal(tail, head) {
-                    if step.0 == 0 {
-                        tail.0 = head.0;
-                    } else {
-                        tail.1 = head.1;
+    fn part1(moves: &Self::Input<'_>) -> Result<Self::Output1, Self::Error> {
+        let mut head = (0, 0);
+        let mut tail = (0, 0);
+        let mut result: HashSet<(i32, i32)> = HashSet::new();
+        result.insert(tail);
+
+        for &(step, count) in moves {
+            for _ in 0..count {
+                head.0 += step.0;
+                head.1 += step.1;
+
+                if far(tail, head) {
+                    if diagonal(tail, head) {
+                        if step.0 == 0 {
+                            tail.0 = head.0;
+                        } else {
+                            tail.1 = head.1;
+                        }
                     }
+                    tail.0 += step.0;
+                    tail.1 += step.1;
+
+                    result.insert(tail);
                 }
-                tail.0 += step.0;
-                tail.1 += step.1;
 
-                result.insert(tail.clone());
+                debug!(?head, ?tail, "step");
             }
         }
+        Ok(result.len())
     }
-    result.len().to_string()
-}
-
-fn direction(dir: &str) -> (i32, i32) {
-    let step = match dir {
-        "U" => (1, 0),
-        "D" => (-1, 0),
-        "L" => (0, -1),
-        "R" => (0, 1),
-        _ => {
-            panic!()
-        }
-    };
-    step
-}
 
-pub fn part2(input: &str) -> String {
-    let mut rope = [(0, 0); 10];
-    let mut result: HashSet<(i32, i32)> = HashSet::new();
-    result.insert(*rope.last().unwrap());
-
-    let (_, moves): (&str, Vec<(&str, u32)>) = separated_list1(newline, moves)(input).unwrap();
-
-    for (dir, count) in moves {
-        let step = direction(dir);
-        for _ in 0..count {
-            rope[0].0 += step.0;
-            rope[0].1 += step.1;
-
-            let mut rope_windows = rope.windows_mut::<2>();
-
-            while let Some([ref mut head, ref mut tail]) = rope_windows.next() {
-                {
-                    // println!("{:?}{:?}", head, tail);
-                    let x_range = (head.0 - 1)..=(head.0 + 1);
-                    let y_range = (head.1 - 1)..=(head.1 + 1);
-
-                    let tail_is_connected = x_range
-                        .cartesian_product(y_range)
-                        .any(|tuple| tuple == *tail);
-
-                    if !tail_is_connected {
-                        // println!("{last_head_move:?}");
-                        // move_tail
-                        // let mut new_tail = head.clone();
-                        if head.0 == tail.0 {
-                            if head.1 > tail.1 {
-                                tail.1 += 1;
-                            } else {
-                                tail.1 -= 1;
-                            }
-                        } else if head.1 == tail.1 {
-                            if head.0 > tail.0 {
-                                tail.0 += 1;
-                            } else {
-                                tail.0 -= 1;
-                            }
-                        } else {
-                            // diagonal
-                            // let head_cross_positions = [
-                            //     (head.0 - 1, head.1),
-                            //     (head.0 + 1, head.1),
-                            //     (head.0, head.1 - 1),
-                            //     (head.0, head.1 + 1),
-                            // ];
-                            let x_range = (head.0 - 1)..=(head.0 + 1);
-                            let y_range = (head.1 - 1)..=(head.1 + 1);
-
-                            let head_3x3 = x_range.cartesian_product(y_range).collect::<Vec<_>>();
-
-                            let x_range = (tail.0 - 1)..=(tail.0 + 1);
-                            let y_range = (tail.1 - 1)..=(tail.1 + 1);
-
-                            let maybe_new_tail: Vec<(i32, i32)> = x_range
-                                .cartesian_product(y_range)
-                                .filter(|tuple| head_3x3.contains(tuple))
-                                .collect();
-                            match maybe_new_tail.len() {
-                                2 => {
-                                    let new_head_cross_positions = [
-                                        (head.0 - 1, head.1),
-                                        (head.0 + 1, head.1),
-                                        (head.0, head.1 - 1),
-                                        (head.0, head.1 + 1),
-                                    ];
-                                    let next = maybe_new_tail
-                                        .iter()
-                                        .find(|tuple| new_head_cross_positions.contains(tuple))
-                                        .unwrap();
-                                    *tail = *next;
+    fn part2(moves: &Self::Input<'_>) -> Result<Self::Output2, Self::Error> {
+        let mut rope = [(0, 0); 10];
+        let mut result: HashSet<(i32, i32)> = HashSet::new();
+        result.insert(*rope.last().unwrap());
+
+        for &(step, count) in moves {
+            for _ in 0..count {
+                rope[0].0 += step.0;
+                rope[0].1 += step.1;
+
+                let mut rope_windows = rope.windows_mut::<2>();
+
+                while let Some([ref mut head, ref mut tail]) = rope_windows.next() {
+                    {
+                        let x_range = (head.0 - 1)..=(head.0 + 1);
+                        let y_range = (head.1 - 1)..=(head.1 + 1);
+
+                        let tail_is_connected = x_range
+                            .cartesian_product(y_range)
+                            .any(|tuple| tuple == *tail);
+
+                        if !tail_is_connected {
+                            if head.0 == tail.0 {
+                                if head.1 > tail.1 {
+                                    tail.1 += 1;
+                                } else {
+                                    tail.1 -= 1;
                                 }
-                                1 => {
-                                    *tail = maybe_new_tail[0];
+                            } else if head.1 == tail.1 {
+                                if head.0 > tail.0 {
+                                    tail.0 += 1;
+                                } else {
+                                    tail.0 -= 1;
                                 }
-                                _ => {
-                                    panic!("unknown tail length");
-                                }
-                            };
-                            // *tail = new_tail;
+                            } else {
+                                // diagonal
+                                let x_range = (head.0 - 1)..=(head.0 + 1);
+                                let y_range = (head.1 - 1)..=(head.1 + 1);
+
+                                let head_3x3 =
+                                    x_range.cartesian_product(y_range).collect::<Vec<_>>();
+
+                                let x_range = (tail.0 - 1)..=(tail.0 + 1);
+                                let y_range = (tail.1 - 1)..=(tail.1 + 1);
+
+                                let maybe_new_tail: Vec<(i32, i32)> = x_range
+                                    .cartesian_product(y_range)
+                                    .filter(|tuple| head_3x3.contains(tuple))
+                                    .collect();
+                                match maybe_new_tail.len() {
+                                    2 => {
+                                        let new_head_cross_positions = [
+                                            (head.0 - 1, head.1),
+                                            (head.0 + 1, head.1),
+                                            (head.0, head.1 - 1),
+                                            (head.0, head.1 + 1),
+                                        ];
+                                        let next = maybe_new_tail
+                                            .iter()
+                                            .find(|tuple| new_head_cross_positions.contains(tuple))
+                                            .unwrap();
+                                        *tail = *next;
+                                    }
+                                    1 => {
+                                        *tail = maybe_new_tail[0];
+                                    }
+                                    _ => {
+                                        panic!("unknown tail length");
+                                    }
+                                };
+                            }
                         }
                     }
                 }
-            }
 
-            result.insert(*rope.last().unwrap());
+                result.insert(*rope.last().unwrap());
+                debug!(head = ?rope[0], tail = ?rope[9], "step");
+            }
         }
+        Ok(result.len())
     }
-    result.len().to_string()
+}
+
+pub fn part1(input: &str) -> Result<String, Error> {
+    Day09::solve(Part::One, input)
+}
+
+pub fn part2(input: &str) -> Result<String, Error> {
+    Day09::solve(Part::Two, input)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT_1: &str = "R 4
-U 4
-L 3
-D 1
-R 4
-D 1
-L 5
-R 2";
-
-    const INPUT_2: &str = "R 5
-U 8
-L 8
-D 3
-R 17
-D 10
-L 25
-U 20";
-
     #[test]
     fn part1_works() {
-        let result = part1(INPUT_1);
+        let result = part1(EXAMPLE).unwrap();
         assert_eq!(result, "13");
     }
 
     #[test]
     fn part2_works() {
-        let result = part2(INPUT_2);
+        let result = part2(LARGER_EXAMPLE).unwrap();
         assert_eq!(result, "36");
     }
+
+    #[test]
+    fn invalid_direction_is_located() {
+        let result = part1("R 4\nU 4\nX 3");
+        assert_eq!(
+            result,
+            Err(Error::InvalidDirection {
+                position: Position { line: 3, column: 1 },
+                token: "X".to_string(),
+            })
+        );
+    }
 }
@@ -0,0 +1,55 @@
+use std::collections::HashSet;
+
+use proptest::prelude::*;
+
+use crate::{generate, part1, part2};
+
+/// Cells visited by the last knot, moving every knot one cell at a time.
+fn tail_visits(input: &str, knots: usize) -> usize {
+    let mut rope = vec![(0i32, 0i32); knots];
+    let mut visited = HashSet::from([(0, 0)]);
+
+    for line in input.lines() {
+        let (direction, count) = line.split_once(' ').unwrap();
+        let (dx, dy) = match direction {
+            "U" => (0, 1),
+            "D" => (0, -1),
+            "L" => (-1, 0),
+            "R" => (1, 0),
+            _ => unreachable!(),
+        };
+
+        for _ in 0..count.parse::<u32>().unwrap() {
+            rope[0].0 += dx;
+            rope[0].1 += dy;
+
+            for knot in 1..knots {
+                let (hx, hy) = rope[knot - 1];
+                let (tx, ty) = &mut rope[knot];
+
+                if (hx - *tx).abs() > 1 || (hy - *ty).abs() > 1 {
+                    *tx += (hx - *tx).signum();
+                    *ty += (hy - *ty).signum();
+                }
+            }
+
+            visited.insert(rope[knots - 1]);
+        }
+    }
+
+    visited.len()
+}
+
+proptest! {
+    #[test]
+    fn part1_matches_reference(seed in any::<u64>(), motions in 1..300usize) {
+        let input = generate::input(seed, motions);
+        prop_assert_eq!(part1(&input).unwrap(), tail_visits(&input, 2).to_string());
+    }
+
+    #[test]
+    fn part2_matches_reference(seed in any::<u64>(), motions in 1..300usize) {
+        let input = generate::input(seed, motions);
+        prop_assert_eq!(part2(&input).unwrap(), tail_visits(&input, 10).to_string());
+    }
+}